@@ -150,18 +150,20 @@ class SecuRizzSolanaClient:
             print(f"❌ Audit verification failed: {str(e)}")
             return False
 
-    async def get_audit_proof(self, contract_hash: str) -> Optional[Dict[str, Any]]:
+    async def get_audit_proof(self, contract_hash: str, oracle: Optional[str] = None) -> Optional[Dict[str, Any]]:
         """Get audit proof from blockchain"""
         try:
             if not self.program:
                 await self.initialize()
             
             contract_hash_bytes = bytes.fromhex(contract_hash)
+            # Proofs are keyed per oracle; default to our own submissions
+            oracle_pubkey = PublicKey(oracle) if oracle else self.provider.wallet.public_key
             
             # Fetch audit proof account
             proof_account = await self.program.account.audit_proof.fetch(
                 PublicKey.find_program_address(
-                    [b"audit_proof", contract_hash_bytes],
+                    [b"audit_proof", contract_hash_bytes, bytes(oracle_pubkey)],
                     self.program_id
                 )[0]
             )
@@ -213,14 +215,15 @@ class SecuRizzSolanaClient:
             print(f"❌ Staking failed: {str(e)}")
             return None
 
-    async def claim_rewards(self, user_keypair: Keypair) -> Optional[str]:
-        """Claim staking rewards"""
+    async def claim_rewards(self, user_keypair: Keypair, min_amount: Optional[int] = None) -> Optional[str]:
+        """Claim staking rewards, failing if less than min_amount is claimable"""
         try:
             if not self.program:
                 await self.initialize()
             
             # Create claim transaction
             tx = await self.program.rpc.claim_rewards(
+                min_amount,
                 ctx={
                     "stake_account": {
                         "user": user_keypair.public_key,
@@ -274,7 +277,7 @@ class SecuRizzSolanaClient:
         self,
         proposal_id: int,
         vote_weight: int,
-        support: bool,
+        vote_type: int,  # 0 = no, 1 = yes, 2 = abstain
         user_keypair: Keypair
     ) -> Optional[str]:
         """Vote on governance proposal"""
@@ -286,11 +289,13 @@ class SecuRizzSolanaClient:
             tx = await self.program.rpc.vote_on_proposal(
                 proposal_id,
                 vote_weight,
-                support,
+                vote_type,
                 ctx={
                     "vote_account": {
                         "proposal_id": proposal_id,
-                        "voter": user_keypair.public_key,
+                        "stake_account": {
+                            "user": user_keypair.public_key,
+                        },
                     },
                     "stake_account": {
                         "user": user_keypair.public_key,
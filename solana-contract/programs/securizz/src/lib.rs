@@ -1,7 +1,39 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{set_return_data, MAX_RETURN_DATA};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::log::sol_log_compute_units;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::solana_program::{blake3, ed25519_program, hash, keccak};
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+pub mod token_economics;
+pub mod utils;
+
+pub use token_economics::*;
+use utils::{apply_bps, assert_rent_exempt, validate_bps, SECONDS_PER_DAY};
 
 declare_id!("SecuRizz1111111111111111111111111111111111111");
 
+pub const MAX_TAGS: usize = 5;
+pub const MAX_TAG_LEN: usize = 32;
+pub const MAX_CID_LEN: usize = 100;
+pub const MAX_AUDIT_SCORE: u8 = 100;
+pub const MAX_RISK_SCORE: u64 = 100;
+pub const PROOF_LAYOUT_VERSION: u8 = 19;
+pub const MIN_SUPPORTED_LAYOUT_VERSION: u8 = 18;
+pub const MAX_VERIFICATION_HISTORY: usize = 4;
+pub const MAX_VERIFIERS: usize = 5;
+pub const MAX_HISTOGRAM_BUCKETS: usize = 16;
+pub const MAX_ORACLES: usize = 32;
+pub const MAX_REPORTS: usize = 4;
+pub const MAX_AUDIT_HISTORY: usize = 8;
+pub const MAX_FEE_MINTS: usize = 4;
+pub const MAX_ALLOWED_VERIFIERS: usize = 8;
+pub const MAX_BATCH: usize = 10; // proofs per batch instruction, sized to the compute budget
+pub const MAX_ACTION_PARAMS: usize = ConfigUpdate::INIT_SPACE; // largest queued action
+pub const PROOF_SUMMARY_LEN: usize = 32 + 1 + 1; // contract_address, audit_score, verified
+
 #[program]
 pub mod securizz {
     use super::*;
@@ -14,19 +46,267 @@ pub mod securizz {
         risk_score: u64,
         contract_address: Pubkey,
         audit_score: u8, // 0-100 score
+        hash_algo: Option<u8>,
+        supersedes: Option<[u8; 32]>,
+        tags: Vec<ProofTag>,
+        findings: FindingCategories,
+        content_hash: [u8; 32], // hash of the report bytes pinned at ipfs_cid
+        nonce: u64, // must equal the oracle's next expected nonce
+        pin_attestation: Option<[u8; 64]>, // pinning service signature over ipfs_cid
+        report_cids: Vec<ReportEntry>, // additional reports beyond ipfs_cid
+        tool_version: u32, // analyzer semver packed as major << 16 | minor << 8 | patch
+        upgrade_authority: Option<Pubkey>, // of the audited program at audit time, None = immutable
+        audit_method: u8, // AuditMethod
+        allowed_verifiers: Vec<Pubkey>, // oracles that may verify this proof, empty = any registered verifier
     ) -> Result<()> {
         let audit_proof = &mut ctx.accounts.audit_proof;
-        let clock = Clock::get()?;
+        let now = current_time()?;
+
+        if let Some((_, error)) = invalid_submission_field(audit_score, risk_score, &ipfs_cid) {
+            return Err(error.into());
+        }
+        require!(tool_version >= ctx.accounts.config.min_tool_version, ErrorCode::ToolVersionTooOld);
+        AuditMethod::try_from(audit_method)?;
+        require!(
+            ctx.accounts.oracle_registry.can_submit(&ctx.accounts.oracle.key()),
+            ErrorCode::Unauthorized
+        );
+        require!(tags.len() <= MAX_TAGS, ErrorCode::TagLimitExceeded);
+        for tag in tags.iter() {
+            tag.validate()?;
+        }
+        require!(report_cids.len() <= MAX_REPORTS, ErrorCode::TooManyReports);
+        for report in report_cids.iter() {
+            report.validate()?;
+        }
+        require!(allowed_verifiers.len() <= MAX_ALLOWED_VERIFIERS, ErrorCode::TooManyAllowedVerifiers);
+
+        let hash_algo = HashAlgo::try_from(hash_algo.unwrap_or(HashAlgo::Sha256 as u8))?;
+
+        let reputation = &mut ctx.accounts.oracle_reputation;
+        reputation.oracle = ctx.accounts.oracle.key();
+        reputation.bump = ctx.bumps.oracle_reputation;
+        reputation.record_submission(now, ctx.accounts.config.max_submissions_per_day)?;
+        require!(nonce == reputation.nonce, ErrorCode::InvalidNonce);
+        reputation.nonce += 1;
+
+        enforce_submission_requirement(
+            &ctx.accounts.config,
+            ctx.accounts.oracle.key(),
+            ctx.accounts.oracle_stake.as_ref(),
+            ctx.accounts.oracle_token_account.as_ref(),
+            ctx.accounts.treasury.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.oracle.to_account_info(),
+        )?;
 
         audit_proof.contract_hash = contract_hash;
         audit_proof.report_hash = report_hash;
+        audit_proof.hash_algo = hash_algo as u8;
         audit_proof.ipfs_cid = ipfs_cid;
         audit_proof.risk_score = risk_score;
         audit_proof.contract_address = contract_address;
         audit_proof.audit_score = audit_score;
-        audit_proof.timestamp = clock.unix_timestamp;
+        audit_proof.timestamp = now;
         audit_proof.verified = false;
         audit_proof.oracle = ctx.accounts.oracle.key();
+        audit_proof.supersedes = supersedes;
+        audit_proof.superseded_by = None;
+        audit_proof.tags = tags;
+        audit_proof.commitment = audit_proof.compute_commitment()?;
+        audit_proof.layout_version = PROOF_LAYOUT_VERSION;
+        audit_proof.revoked = false;
+        audit_proof.findings = findings;
+        audit_proof.content_hash = content_hash;
+        audit_proof.bump = ctx.bumps.audit_proof;
+        audit_proof.pin_attestation = pin_attestation;
+        audit_proof.report_cids = report_cids;
+        audit_proof.verifier_signatures = Vec::new();
+        audit_proof.first_signature_time = 0;
+        audit_proof.expired = false;
+        audit_proof.fee_mint = Pubkey::default();
+        audit_proof.tool_version = tool_version;
+        audit_proof.upgrade_authority = upgrade_authority;
+        audit_proof.audit_method = audit_method;
+        audit_proof.allowed_verifiers = allowed_verifiers;
+        audit_proof.version = 1;
+        audit_proof.sealed = false;
+        audit_proof.oracle_rewarded = false;
+
+        // The pinning service signs the CID; its signature must be checked by the
+        // ed25519 program in the instruction immediately before this one
+        if let Some(pin_sig) = pin_attestation {
+            let instructions = ctx.accounts.instructions.as_ref().ok_or(ErrorCode::InvalidSignature)?;
+            let instructions = instructions.to_account_info();
+            let current_index = load_current_index_checked(&instructions)? as usize;
+            require!(current_index > 0, ErrorCode::InvalidSignature);
+            let ed25519_ix = load_instruction_at_checked(current_index - 1, &instructions)?;
+            verify_ed25519_ix(
+                &ed25519_ix,
+                &ctx.accounts.config.pinning_pubkey,
+                &pin_sig,
+                audit_proof.ipfs_cid.as_bytes(),
+            )?;
+        }
+
+        // Reused addresses always resolve to the newest proof
+        let address_index = &mut ctx.accounts.address_index;
+        address_index.contract_address = contract_address;
+        address_index.contract_hash = contract_hash;
+        address_index.oracle = audit_proof.oracle;
+        address_index.updated_at = now;
+        address_index.bump = ctx.bumps.address_index;
+
+        let audit_history = &mut ctx.accounts.audit_history;
+        audit_history.contract_address = contract_address;
+        audit_history.bump = ctx.bumps.audit_history;
+        audit_history.record(audit_proof, now);
+
+        assert_rent_exempt(&audit_proof.to_account_info())?;
+
+        emit!(ProofSubmitted {
+            contract_hash,
+            report_hash,
+            contract_address,
+            audit_score,
+            risk_score,
+            timestamp: now,
+            oracle: ctx.accounts.oracle.key(),
+            nonce,
+            tool_version,
+        });
+
+        if let Some(old_hash) = supersedes {
+            let oracle = ctx.accounts.oracle.key();
+            let admin = ctx.accounts.config.admin;
+            let prior_proof = ctx
+                .accounts
+                .prior_proof
+                .as_mut()
+                .ok_or(ErrorCode::ProofNotFound)?;
+
+            require!(prior_proof.contract_hash == old_hash, ErrorCode::ProofNotFound);
+            // Only the prior proof's own oracle (or the admin) may retire it, and only
+            // with a re-audit of the same deployed contract
+            require!(prior_proof.oracle == oracle || oracle == admin, ErrorCode::Unauthorized);
+            require!(prior_proof.contract_address == contract_address, ErrorCode::ContractMismatch);
+            require!(prior_proof.superseded_by.is_none(), ErrorCode::AlreadySuperseded);
+
+            prior_proof.superseded_by = Some(contract_hash);
+
+            emit!(ProofSuperseded {
+                old_hash,
+                new_hash: contract_hash,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn submit_proof_attested(
+        ctx: Context<SubmitProofAttested>,
+        contract_hash: [u8; 32],
+        report_hash: [u8; 32],
+        ipfs_cid: String,
+        risk_score: u64,
+        contract_address: Pubkey,
+        audit_score: u8, // 0-100 score
+        oracle_sig: [u8; 64],
+        oracle_pubkey: Pubkey,
+        content_hash: [u8; 32],
+        tool_version: u32, // covered by the oracle's signature
+        upgrade_authority: Option<Pubkey>, // covered by the oracle's signature
+        audit_method: u8, // covered by the oracle's signature
+        nonce: u64, // covered by the oracle's signature, must equal its next expected nonce
+    ) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        let now = current_time()?;
+
+        if let Some((_, error)) = invalid_submission_field(audit_score, risk_score, &ipfs_cid) {
+            return Err(error.into());
+        }
+        require!(tool_version >= ctx.accounts.config.min_tool_version, ErrorCode::ToolVersionTooOld);
+        AuditMethod::try_from(audit_method)?;
+        require!(ctx.accounts.oracle_registry.can_submit(&oracle_pubkey), ErrorCode::Unauthorized);
+
+        // The signed nonce keeps a signature from being replayed once the proof is closed
+        let reputation = &mut ctx.accounts.oracle_reputation;
+        reputation.oracle = oracle_pubkey;
+        reputation.bump = ctx.bumps.oracle_reputation;
+        reputation.record_submission(now, ctx.accounts.config.max_submissions_per_day)?;
+        require!(nonce == reputation.nonce, ErrorCode::InvalidNonce);
+        reputation.nonce += 1;
+
+        // The submitter relays the proof, so it pays the fee when the oracle is under-staked
+        enforce_submission_requirement(
+            &ctx.accounts.config,
+            oracle_pubkey,
+            ctx.accounts.oracle_stake.as_ref(),
+            ctx.accounts.submitter_token_account.as_ref(),
+            ctx.accounts.treasury.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.submitter.to_account_info(),
+        )?;
+
+        audit_proof.contract_hash = contract_hash;
+        audit_proof.report_hash = report_hash;
+        audit_proof.hash_algo = HashAlgo::Sha256 as u8;
+        audit_proof.ipfs_cid = ipfs_cid;
+        audit_proof.risk_score = risk_score;
+        audit_proof.contract_address = contract_address;
+        audit_proof.audit_score = audit_score;
+        audit_proof.timestamp = now;
+        audit_proof.verified = false;
+        audit_proof.oracle = oracle_pubkey;
+        audit_proof.supersedes = None;
+        audit_proof.superseded_by = None;
+        audit_proof.tags = Vec::new();
+        audit_proof.commitment = audit_proof.compute_commitment()?;
+        audit_proof.layout_version = PROOF_LAYOUT_VERSION;
+        audit_proof.revoked = false;
+        audit_proof.findings = FindingCategories::default();
+        audit_proof.content_hash = content_hash;
+        audit_proof.bump = ctx.bumps.audit_proof;
+        audit_proof.pin_attestation = None;
+        audit_proof.report_cids = Vec::new();
+        audit_proof.verifier_signatures = Vec::new();
+        audit_proof.first_signature_time = 0;
+        audit_proof.expired = false;
+        audit_proof.fee_mint = Pubkey::default();
+        audit_proof.tool_version = tool_version;
+        audit_proof.upgrade_authority = upgrade_authority;
+        audit_proof.audit_method = audit_method;
+        audit_proof.allowed_verifiers = Vec::new();
+        audit_proof.version = 1;
+        audit_proof.sealed = false;
+        audit_proof.oracle_rewarded = false;
+
+        // The oracle signs off-chain; its signature must be checked by the
+        // ed25519 program in the instruction immediately before this one
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&instructions)? as usize;
+        require!(current_index > 0, ErrorCode::InvalidSignature);
+        let ed25519_ix = load_instruction_at_checked(current_index - 1, &instructions)?;
+        verify_ed25519_ix(
+            &ed25519_ix,
+            &oracle_pubkey,
+            &oracle_sig,
+            &audit_proof.attestation_message(nonce),
+        )?;
+
+        let address_index = &mut ctx.accounts.address_index;
+        address_index.contract_address = contract_address;
+        address_index.contract_hash = contract_hash;
+        address_index.oracle = oracle_pubkey;
+        address_index.updated_at = now;
+        address_index.bump = ctx.bumps.address_index;
+
+        let audit_history = &mut ctx.accounts.audit_history;
+        audit_history.contract_address = contract_address;
+        audit_history.bump = ctx.bumps.audit_history;
+        audit_history.record(audit_proof, now);
+
+        assert_rent_exempt(&audit_proof.to_account_info())?;
 
         emit!(ProofSubmitted {
             contract_hash,
@@ -34,7 +314,10 @@ pub mod securizz {
             contract_address,
             audit_score,
             risk_score,
-            timestamp: clock.unix_timestamp,
+            timestamp: now,
+            oracle: oracle_pubkey,
+            nonce,
+            tool_version,
         });
 
         Ok(())
@@ -45,166 +328,3433 @@ pub mod securizz {
         verified: bool,
     ) -> Result<()> {
         let audit_proof = &mut ctx.accounts.audit_proof;
+        let authority = ctx.accounts.authority.key();
         
         require!(
-            ctx.accounts.authority.key() == audit_proof.oracle,
-            ErrorCode::Unauthorized
+            ctx.accounts.oracle_stake.amount >= ctx.accounts.config.min_oracle_stake,
+            ErrorCode::InsufficientOracleStake
         );
 
-        audit_proof.verified = verified;
+        // Reported below; add_verifier_signature applies it
+        let required_signatures = ctx.accounts.config.risk_bands.required_signatures(audit_proof.risk_score);
+
+        if verified {
+            let now = current_time()?;
+            let completed = add_verifier_signature(
+                audit_proof,
+                authority,
+                &ctx.accounts.oracle_registry,
+                &ctx.accounts.config,
+                now,
+            )?;
+
+            if completed {
+                if let Some(audit_history) = ctx.accounts.audit_history.as_mut() {
+                    audit_history.record(audit_proof, now);
+                }
+            }
+        } else {
+            // Only the submitting oracle may withdraw its proof from verification
+            require!(authority == audit_proof.oracle, ErrorCode::Unauthorized);
+            audit_proof.verified = false;
+            audit_proof.verifier_signatures.clear();
+            audit_proof.first_signature_time = 0;
+        }
 
         emit!(VerificationUpdated {
+            contract_hash: audit_proof.contract_hash,
+            verified: audit_proof.verified,
+            signatures: audit_proof.verifier_signatures.len() as u8,
+            required_signatures,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_verified(
+        ctx: Context<SetVerified>,
+        verified: bool,
+        reason_cid: String,
+    ) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        let history = &mut ctx.accounts.verification_history;
+        let now = current_time()?;
+
+        validate_cid_len(&reason_cid)?;
+
+        if verified {
+            audit_proof.mark_verified(now, ctx.accounts.config.verification_ttl);
+        } else {
+            audit_proof.verified = false;
+            audit_proof.verifier_signatures.clear();
+            audit_proof.first_signature_time = 0;
+        }
+
+        if let Some(audit_history) = ctx.accounts.audit_history.as_mut() {
+            audit_history.record(audit_proof, now);
+        }
+
+        // Keep only the most recent overrides
+        if history.entries.len() >= MAX_VERIFICATION_HISTORY {
+            history.entries.remove(0);
+        }
+        history.audit_proof = audit_proof.key();
+        history.bump = ctx.bumps.verification_history;
+        history.entries.push(VerificationChange {
+            verified,
+            admin: ctx.accounts.admin.key(),
+            timestamp: now,
+            reason_cid: reason_cid.clone(),
+        });
+
+        emit!(VerificationChanged {
             contract_hash: audit_proof.contract_hash,
             verified,
+            reason_cid,
+        });
+
+        Ok(())
+    }
+
+    pub fn revoke_proof(ctx: Context<RevokeProof>) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+
+        require!(!audit_proof.revoked, ErrorCode::ProofRevoked);
+
+        audit_proof.revoked = true;
+
+        emit!(ProofRevoked {
+            contract_hash: audit_proof.contract_hash,
+            admin: ctx.accounts.admin.key(),
+        });
+
+        Ok(())
+    }
+
+    // Keeper-callable: flag a proof whose verification has lapsed and pay the caller a fee
+    pub fn sweep_expired(ctx: Context<SweepExpired>) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        let now = current_time()?;
+
+        require!(!audit_proof.expired, ErrorCode::AlreadySwept);
+        require!(
+            audit_proof.expiry_timestamp != 0 && now > audit_proof.expiry_timestamp,
+            ErrorCode::ProofNotExpired
+        );
+
+        audit_proof.expired = true;
+
+        let fee = ctx.accounts.config.expiry_sweep_fee;
+        if fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.keeper_token_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+            let signer_seeds: &[&[&[u8]]] = &[&[b"treasury_authority", &[ctx.bumps.treasury_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, fee)?;
+        }
+
+        emit!(ProofExpiredSwept {
+            contract_hash: audit_proof.contract_hash,
+            keeper: ctx.accounts.keeper.key(),
         });
 
         Ok(())
     }
 
+    // Close a proof that is no longer authoritative, returning its rent to the oracle
+    // less the treasury's close_fee_bps cut. An address index still pointing at the proof
+    // is closed with it so lookups stop resolving to a missing account
+    pub fn close_proof(ctx: Context<CloseProof>) -> Result<()> {
+        let audit_proof = &ctx.accounts.audit_proof;
+        require!(
+            audit_proof.revoked || audit_proof.superseded_by.is_some() || audit_proof.expired,
+            ErrorCode::ProofStillActive
+        );
+
+        let oracle_info = ctx.accounts.oracle.to_account_info();
+        let index_info = ctx.accounts.address_index.to_account_info();
+        if index_info.owner == &crate::ID && !index_info.data_is_empty() {
+            let address_index = AddressIndex::try_deserialize(&mut &index_info.data.borrow()[..])?;
+            if address_index.contract_hash == audit_proof.contract_hash
+                && address_index.oracle == audit_proof.oracle
+            {
+                **oracle_info.try_borrow_mut_lamports()? = oracle_info
+                    .lamports()
+                    .checked_add(index_info.lamports())
+                    .ok_or(ErrorCode::MathOverflow)?;
+                **index_info.try_borrow_mut_lamports()? = 0;
+                index_info.assign(&system_program::ID);
+                index_info.realloc(0, false)?;
+            }
+        }
+
+        let proof_info = audit_proof.to_account_info();
+        let reclaimed = proof_info.lamports();
+        let fee = apply_bps(reclaimed, ctx.accounts.config.close_fee_bps);
+
+        // Split by hand since `close = ` can only send everything to one account
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        **treasury_info.try_borrow_mut_lamports()? = treasury_info
+            .lamports()
+            .checked_add(fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        **oracle_info.try_borrow_mut_lamports()? = oracle_info
+            .lamports()
+            .checked_add(reclaimed - fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        **proof_info.try_borrow_mut_lamports()? = 0;
+
+        proof_info.assign(&system_program::ID);
+        proof_info.realloc(0, false)?;
+
+        emit!(CloseFeeCollected { amount: fee });
+
+        Ok(())
+    }
+
     pub fn get_proof(ctx: Context<GetProof>) -> Result<()> {
         let audit_proof = &ctx.accounts.audit_proof;
         
+        // Guard against accounts that deserialize but carry an unknown layout
+        require!(
+            (MIN_SUPPORTED_LAYOUT_VERSION..=PROOF_LAYOUT_VERSION).contains(&audit_proof.layout_version),
+            ErrorCode::UnsupportedLayout
+        );
+        require!(audit_proof.ipfs_cid.len() <= MAX_CID_LEN, ErrorCode::InvalidIpfsCid);
+        
+        let now = current_time()?;
+        let stale = audit_proof.is_stale(now, ctx.accounts.config.verification_ttl);
+        
         emit!(ProofRetrieved {
             contract_hash: audit_proof.contract_hash,
             report_hash: audit_proof.report_hash,
+            hash_algo: audit_proof.hash_algo,
             contract_address: audit_proof.contract_address,
             ipfs_cid: audit_proof.ipfs_cid.clone(),
             audit_score: audit_proof.audit_score,
             risk_score: audit_proof.risk_score,
             timestamp: audit_proof.timestamp,
             verified: audit_proof.verified,
+            stale,
+            tags: audit_proof.tags.clone(),
+            findings: audit_proof.findings,
+            report_cids: audit_proof.report_cids.clone(),
+            tool_version: audit_proof.tool_version,
+            audit_method: audit_proof.audit_method,
         });
 
         Ok(())
     }
 
-    pub fn verify_audit_integrity(
-        ctx: Context<VerifyIntegrity>,
-        expected_ipfs_hash: [u8; 32],
-    ) -> Result<()> {
+    pub fn set_risk_score(ctx: Context<UpdateProofField>, risk_score: u64) -> Result<()> {
+        require!(risk_score <= MAX_RISK_SCORE, ErrorCode::InvalidRiskScore);
+
         let audit_proof = &mut ctx.accounts.audit_proof;
-        let clock = Clock::get()?;
-        
-        // Verify IPFS hash integrity
-        let stored_hash = audit_proof.report_hash;
-        require!(
-            stored_hash == expected_ipfs_hash,
-            ErrorCode::HashMismatch
-        );
-        
-        audit_proof.verified = true;
-        audit_proof.verification_timestamp = clock.unix_timestamp;
-        
-        emit!(AuditVerified {
+        audit_proof.risk_score = risk_score;
+        audit_proof.field_updated()?;
+
+        emit!(ProofFieldUpdated {
             contract_hash: audit_proof.contract_hash,
-            contract_address: audit_proof.contract_address,
-            audit_score: audit_proof.audit_score,
-            verification_timestamp: clock.unix_timestamp,
+            field: "risk_score".to_string(),
+            version: audit_proof.version,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(contract_hash: [u8; 32])]
-pub struct SubmitProof<'info> {
-    #[account(
-        init,
-        payer = oracle,
-        space = 8 + 32 + 32 + 4 + 100 + 32 + 1 + 8 + 8 + 8 + 1 + 32,
-        seeds = [b"audit_proof", contract_hash.as_ref()],
-        bump
-    )]
-    pub audit_proof: Account<'info, AuditProof>,
-    
-    #[account(mut)]
-    pub oracle: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn set_ipfs_cid(ctx: Context<UpdateProofField>, ipfs_cid: String) -> Result<()> {
+        require!(!ipfs_cid.is_empty(), ErrorCode::InvalidIpfsCid);
+        validate_cid_len(&ipfs_cid)?;
 
-#[derive(Accounts)]
-pub struct UpdateVerification<'info> {
-    #[account(mut)]
-    pub audit_proof: Account<'info, AuditProof>,
-    
-    pub authority: Signer<'info>,
-}
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        audit_proof.ipfs_cid = ipfs_cid;
+        // The pinning service signed the old CID
+        audit_proof.pin_attestation = None;
+        audit_proof.field_updated()?;
 
-#[derive(Accounts)]
-pub struct GetProof<'info> {
-    pub audit_proof: Account<'info, AuditProof>,
-}
+        emit!(ProofFieldUpdated {
+            contract_hash: audit_proof.contract_hash,
+            field: "ipfs_cid".to_string(),
+            version: audit_proof.version,
+        });
 
-#[derive(Accounts)]
-pub struct VerifyIntegrity<'info> {
-    #[account(mut)]
-    pub audit_proof: Account<'info, AuditProof>,
-    
-    pub authority: Signer<'info>,
-}
+        Ok(())
+    }
 
-#[account]
-pub struct AuditProof {
-    pub contract_hash: [u8; 32],
-    pub report_hash: [u8; 32],
-    pub ipfs_cid: String,
-    pub contract_address: Pubkey,
-    pub audit_score: u8, // 0-100 score
-    pub risk_score: u64,
-    pub timestamp: i64,
-    pub verification_timestamp: i64,
-    pub verified: bool,
-    pub oracle: Pubkey,
-}
+    pub fn set_audit_score(ctx: Context<UpdateProofField>, audit_score: u8) -> Result<()> {
+        require!(audit_score <= MAX_AUDIT_SCORE, ErrorCode::InvalidAuditScore);
 
-#[event]
-pub struct ProofSubmitted {
-    pub contract_hash: [u8; 32],
-    pub report_hash: [u8; 32],
-    pub contract_address: Pubkey,
-    pub audit_score: u8,
-    pub risk_score: u64,
-    pub timestamp: i64,
-}
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        audit_proof.audit_score = audit_score;
+        audit_proof.field_updated()?;
 
-#[event]
-pub struct VerificationUpdated {
-    pub contract_hash: [u8; 32],
-    pub verified: bool,
-}
+        emit!(ProofFieldUpdated {
+            contract_hash: audit_proof.contract_hash,
+            field: "audit_score".to_string(),
+            version: audit_proof.version,
+        });
 
-#[event]
-pub struct ProofRetrieved {
-    pub contract_hash: [u8; 32],
-    pub report_hash: [u8; 32],
-    pub contract_address: Pubkey,
-    pub ipfs_cid: String,
-    pub audit_score: u8,
-    pub risk_score: u64,
-    pub timestamp: i64,
-    pub verified: bool,
-}
+        Ok(())
+    }
 
-#[event]
-pub struct AuditVerified {
-    pub contract_hash: [u8; 32],
-    pub contract_address: Pubkey,
-    pub audit_score: u8,
-    pub verification_timestamp: i64,
-}
+    pub fn add_report(ctx: Context<UpdateProofField>, kind: ReportKind, cid: String) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Unauthorized access")]
-    Unauthorized,
-    #[msg("Invalid risk score")]
-    InvalidRiskScore,
-    #[msg("Proof not found")]
-    ProofNotFound,
-    #[msg("Hash mismatch")]
-    HashMismatch,
-    #[msg("Invalid audit score")]
-    InvalidAuditScore,
+        require!(audit_proof.report_cids.len() < MAX_REPORTS, ErrorCode::TooManyReports);
+        let report = ReportEntry { kind, cid };
+        report.validate()?;
+        audit_proof.report_cids.push(report);
+
+        emit!(ReportAdded {
+            contract_hash: audit_proof.contract_hash,
+            kind,
+            count: audit_proof.report_cids.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    // Freeze the proof's fields and bind them all under one commitment
+    pub fn seal_proof(ctx: Context<UpdateProofField>) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+
+        audit_proof.sealed = true;
+        audit_proof.commitment = audit_proof.compute_commitment()?;
+
+        emit!(ProofSealed {
+            contract_hash: audit_proof.contract_hash,
+            commitment: audit_proof.commitment,
+        });
+
+        Ok(())
+    }
+
+    pub fn add_tag(
+        ctx: Context<AddTag>,
+        key: String,
+        value: String,
+    ) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+
+        require!(
+            ctx.accounts.authority.key() == audit_proof.oracle,
+            ErrorCode::Unauthorized
+        );
+        require!(!audit_proof.sealed, ErrorCode::ProofSealed);
+        require!(audit_proof.tags.len() < MAX_TAGS, ErrorCode::TagLimitExceeded);
+
+        let tag = ProofTag { key, value };
+        tag.validate()?;
+        audit_proof.tags.push(tag);
+
+        Ok(())
+    }
+
+    // The proof's seeds are passed in because an older layout can't be read
+    // before the account is resized
+    pub fn migrate_proof(
+        ctx: Context<MigrateProof>,
+        _contract_hash: [u8; 32],
+        _oracle: Pubkey,
+    ) -> Result<()> {
+        let proof_info = ctx.accounts.audit_proof.to_account_info();
+        let authority = ctx.accounts.authority.key();
+
+        // Grow the account first so the older, shorter layout deserializes with zeroed new fields
+        let space = 8 + AuditProof::INIT_SPACE;
+        if proof_info.data_len() < space {
+            let rent = Rent::get()?;
+            let lamports_needed = rent
+                .minimum_balance(space)
+                .saturating_sub(proof_info.lamports());
+            if lamports_needed > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: proof_info.clone(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, lamports_needed)?;
+            }
+            proof_info.realloc(space, true)?;
+        }
+
+        let mut audit_proof = AuditProof::try_deserialize(&mut &proof_info.try_borrow_data()?[..])?;
+
+        require!(
+            authority == audit_proof.oracle || authority == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            audit_proof.layout_version < PROOF_LAYOUT_VERSION,
+            ErrorCode::AlreadyMigrated
+        );
+
+        let from_version = audit_proof.layout_version;
+        audit_proof.layout_version = PROOF_LAYOUT_VERSION;
+        // Older layouts predate the stored bump; record the one the seeds check derived
+        audit_proof.bump = ctx.bumps.audit_proof;
+        audit_proof.try_serialize(&mut &mut proof_info.try_borrow_mut_data()?[..])?;
+
+        emit!(ProofMigrated {
+            contract_hash: audit_proof.contract_hash,
+            from_version,
+            to_version: PROOF_LAYOUT_VERSION,
+        });
+
+        Ok(())
+    }
+
+    pub fn check_verified(ctx: Context<CheckVerified>) -> Result<bool> {
+        // Logged on entry so tests can measure account validation, where the stored
+        // bump saves re-deriving the proof PDA
+        sol_log_compute_units();
+        let now = current_time()?;
+        check_verified_proof(&ctx.accounts.audit_proof, &ctx.accounts.config, now)
+    }
+
+    // Same answer as check_verified, keyed by the deployed contract address through
+    // its index entry instead of the proof PDA
+    pub fn check_verified_by_address(ctx: Context<CheckVerifiedByAddress>) -> Result<bool> {
+        let now = current_time()?;
+        check_verified_proof(&ctx.accounts.audit_proof, &ctx.accounts.config, now)
+    }
+
+    pub fn get_effective_score(ctx: Context<GetProof>) -> Result<u8> {
+        let audit_proof = &ctx.accounts.audit_proof;
+        let now = current_time()?;
+
+        let score = audit_proof.effective_score(now, ctx.accounts.config.decay_bps_per_day);
+
+        emit!(EffectiveScore {
+            contract_hash: audit_proof.contract_hash,
+            score,
+        });
+
+        Ok(score)
+    }
+
+    // Upgradeable programs can change after the audit, so consumers may treat them as riskier
+    pub fn check_mutability(ctx: Context<GetProof>) -> Result<()> {
+        let audit_proof = &ctx.accounts.audit_proof;
+
+        emit!(MutabilityStatus {
+            contract_address: audit_proof.contract_address,
+            mutable: audit_proof.upgrade_authority.is_some(),
+            upgrade_authority: audit_proof.upgrade_authority,
+        });
+
+        Ok(())
+    }
+
+    pub fn has_category(ctx: Context<GetProof>, category: u8) -> Result<bool> {
+        let count = ctx.accounts.audit_proof.findings.count(FindingCategory::try_from(category)?);
+
+        Ok(count > 0)
+    }
+
+    pub fn resolve_by_address(ctx: Context<ResolveAddress>) -> Result<()> {
+        let address_index = &ctx.accounts.address_index;
+
+        emit!(AddressResolved {
+            contract_address: address_index.contract_address,
+            contract_hash: address_index.contract_hash,
+            oracle: address_index.oracle,
+        });
+
+        Ok(())
+    }
+
+    pub fn get_history(ctx: Context<GetHistory>) -> Result<()> {
+        let audit_history = &ctx.accounts.audit_history;
+
+        emit!(AuditHistoryRetrieved {
+            contract_address: audit_history.contract_address,
+            entries: audit_history.entries.clone(),
+        });
+
+        Ok(())
+    }
+
+    // Dry-run of the submit_proof field checks so clients can fix input before paying rent
+    pub fn validate_submission(
+        _ctx: Context<ValidateSubmission>,
+        audit_score: u8,
+        risk_score: u64,
+        ipfs_cid: String,
+    ) -> Result<bool> {
+        let failing_field = invalid_submission_field(audit_score, risk_score, &ipfs_cid)
+            .map(|(field, _)| field.to_string());
+        let ok = failing_field.is_none();
+
+        emit!(SubmissionValidation { ok, failing_field });
+
+        Ok(ok)
+    }
+
+    pub fn find_proof(
+        ctx: Context<FindProof>,
+        contract_hash: [u8; 32],
+        oracle: Pubkey,
+    ) -> Result<bool> {
+        let proof_info = ctx.accounts.audit_proof.to_account_info();
+        let exists = proof_info.owner == &crate::ID && !proof_info.data_is_empty();
+
+        emit!(ProofLookup {
+            contract_hash,
+            oracle,
+            exists,
+        });
+
+        Ok(exists)
+    }
+
+    // Cheaper than loading the proof: only lamports, owner and size are read.
+    // Proofs still on an older layout have a different size and report false
+    pub fn proof_exists(
+        ctx: Context<FindProof>,
+        contract_hash: [u8; 32],
+        _oracle: Pubkey,
+    ) -> Result<bool> {
+        let proof_info = ctx.accounts.audit_proof.to_account_info();
+        let exists = proof_info.lamports() > 0
+            && proof_info.owner == ctx.program_id
+            && proof_info.data_len() == 8 + AuditProof::INIT_SPACE;
+
+        emit!(ProofExists {
+            contract_hash,
+            exists,
+        });
+
+        Ok(exists)
+    }
+
+    // Diff the scores of a re-audit against an earlier proof of the same contract
+    pub fn compare_proofs(ctx: Context<CompareProofs>) -> Result<()> {
+        let old_proof = &ctx.accounts.old_proof;
+        let new_proof = &ctx.accounts.new_proof;
+
+        // Better on one axis without getting worse on the other
+        let improved = new_proof.audit_score >= old_proof.audit_score
+            && new_proof.risk_score <= old_proof.risk_score
+            && (new_proof.audit_score > old_proof.audit_score || new_proof.risk_score < old_proof.risk_score);
+
+        let comparison = ProofComparison {
+            contract_address: new_proof.contract_address,
+            old_score: old_proof.audit_score,
+            new_score: new_proof.audit_score,
+            old_risk: old_proof.risk_score,
+            new_risk: new_proof.risk_score,
+            improved,
+        };
+
+        set_return_data(&comparison.try_to_vec()?);
+        emit!(comparison);
+
+        Ok(())
+    }
+
+    // The AuditProof serialization behind a truncation flag byte. The ipfs_cid is
+    // shortened to fit MAX_RETURN_DATA; get_proof emits the proof in full
+    pub fn get_proof_data(ctx: Context<GetProof>) -> Result<()> {
+        let mut audit_proof = (*ctx.accounts.audit_proof).clone();
+        let mut data = audit_proof.try_to_vec()?;
+        let mut truncated = false;
+
+        if data.len() + 1 > MAX_RETURN_DATA {
+            let overflow = data.len() + 1 - MAX_RETURN_DATA;
+            require!(overflow <= audit_proof.ipfs_cid.len(), ErrorCode::ReturnDataTooLarge);
+
+            let mut cid_len = audit_proof.ipfs_cid.len() - overflow;
+            while !audit_proof.ipfs_cid.is_char_boundary(cid_len) {
+                cid_len -= 1;
+            }
+            audit_proof.ipfs_cid.truncate(cid_len);
+            data = audit_proof.try_to_vec()?;
+            truncated = true;
+        }
+
+        let mut return_data = Vec::with_capacity(data.len() + 1);
+        return_data.push(truncated as u8);
+        return_data.extend_from_slice(&data);
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+
+    pub fn verify_audit_integrity(
+        ctx: Context<VerifyIntegrity>,
+        expected_ipfs_hash: [u8; 32],
+        check_commitment: bool,
+        content_hash: [u8; 32], // recomputed by the caller from the fetched report
+    ) -> Result<()> {
+        let audit_proof = &mut ctx.accounts.audit_proof;
+        let now = current_time()?;
+        
+        // The verifier must still have stake at risk when it signs off
+        require!(
+            ctx.accounts.oracle_stake.amount >= ctx.accounts.config.min_oracle_stake,
+            ErrorCode::InsufficientOracleStake
+        );
+        
+        // Verify IPFS hash integrity
+        let stored_hash = audit_proof.report_hash;
+        require!(
+            stored_hash == expected_ipfs_hash,
+            ErrorCode::HashMismatch
+        );
+        
+        // The CID alone does not pin the report; a swapped CID serving different
+        // bytes shows up as a content hash mismatch
+        require!(audit_proof.content_hash == content_hash, ErrorCode::HashMismatch);
+        // A failed check rolls back its logs, so only a match is ever observed
+        emit!(ContentVerified {
+            contract_hash: audit_proof.contract_hash,
+            matched: true,
+        });
+        
+        // Recompute the commitment on-chain rather than trusting the caller
+        if check_commitment {
+            require!(
+                audit_proof.compute_commitment()? == audit_proof.commitment,
+                ErrorCode::HashMismatch
+            );
+        }
+        
+        // A passing check counts as this verifier's signature toward the quorum
+        let completed = add_verifier_signature(
+            audit_proof,
+            ctx.accounts.authority.key(),
+            &ctx.accounts.oracle_registry,
+            &ctx.accounts.config,
+            now,
+        )?;
+        
+        if completed {
+            emit!(AuditVerified {
+                contract_hash: audit_proof.contract_hash,
+                contract_address: audit_proof.contract_address,
+                audit_score: audit_proof.audit_score,
+                verification_timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyBatch<'info>>,
+        expected_hashes: Vec<[u8; 32]>,
+        skip_mismatches: bool,
+    ) -> Result<()> {
+        let proof_accounts = ctx.remaining_accounts;
+        let now = current_time()?;
+
+        // Reject oversized batches up front rather than running out of compute mid-loop
+        require!(
+            proof_accounts.len() == expected_hashes.len() && proof_accounts.len() <= MAX_BATCH,
+            ErrorCode::AccountCountMismatch
+        );
+
+        // Batches are self-verification, which split duties do not allow
+        let authority = ctx.accounts.authority.key();
+        let registry = &ctx.accounts.oracle_registry;
+        require!(registry.can_verify(&authority), ErrorCode::Unauthorized);
+        require!(!registry.roles_split(), ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.oracle_stake.amount >= ctx.accounts.config.min_oracle_stake,
+            ErrorCode::InsufficientOracleStake
+        );
+
+        let mut count: u32 = 0;
+        for (account_info, expected_hash) in proof_accounts.iter().zip(expected_hashes.iter()) {
+            let mut audit_proof = Account::<AuditProof>::try_from(account_info)?;
+
+            require!(authority == audit_proof.oracle, ErrorCode::Unauthorized);
+
+            // Already verified or signed proofs are left untouched
+            if audit_proof.verified || audit_proof.verifier_signatures.contains(&authority) {
+                continue;
+            }
+
+            if audit_proof.report_hash != *expected_hash {
+                require!(skip_mismatches, ErrorCode::HashMismatch);
+                continue;
+            }
+
+            // The batch only signs; proofs whose risk band needs more signatures stay pending
+            let completed = add_verifier_signature(&mut audit_proof, authority, registry, &ctx.accounts.config, now)?;
+            audit_proof.exit(ctx.program_id)?;
+            if !completed {
+                continue;
+            }
+            count += 1;
+
+            emit!(AuditVerified {
+                contract_hash: audit_proof.contract_hash,
+                contract_address: audit_proof.contract_address,
+                audit_score: audit_proof.audit_score,
+                verification_timestamp: now,
+            });
+        }
+
+        emit!(BatchVerified {
+            oracle: ctx.accounts.authority.key(),
+            count,
+        });
+
+        Ok(())
+    }
+
+    pub fn compute_portfolio_risk<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReadProofs>,
+        weights: Vec<u64>,
+    ) -> Result<u64> {
+        let proof_accounts = ctx.remaining_accounts;
+
+        require!(
+            proof_accounts.len() == weights.len() && proof_accounts.len() <= MAX_BATCH,
+            ErrorCode::AccountCountMismatch
+        );
+
+        let mut weighted_sum: u128 = 0;
+        let mut total_weight: u128 = 0;
+        for (account_info, weight) in proof_accounts.iter().zip(weights.iter()) {
+            let audit_proof = Account::<AuditProof>::try_from(account_info)?;
+
+            let weighted_risk = (audit_proof.risk_score as u128)
+                .checked_mul(*weight as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            weighted_sum = weighted_sum.checked_add(weighted_risk).ok_or(ErrorCode::MathOverflow)?;
+            total_weight = total_weight.checked_add(*weight as u128).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // An empty portfolio or all-zero weights has no risk to average
+        let average = if total_weight == 0 { 0 } else { (weighted_sum / total_weight) as u64 };
+
+        emit!(PortfolioRisk {
+            average,
+            count: proof_accounts.len() as u32,
+        });
+
+        Ok(average)
+    }
+
+    // counts[i] holds proofs with buckets[i - 1] <= risk_score < buckets[i]; the
+    // first and last counts are open-ended, so there is one more count than boundary
+    pub fn risk_histogram<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReadProofs>,
+        buckets: Vec<u64>,
+    ) -> Result<Vec<u32>> {
+        require!(
+            !buckets.is_empty() && buckets.len() <= MAX_HISTOGRAM_BUCKETS,
+            ErrorCode::InvalidBuckets
+        );
+        require!(
+            buckets.windows(2).all(|pair| pair[0] < pair[1]),
+            ErrorCode::InvalidBuckets
+        );
+        require!(ctx.remaining_accounts.len() <= MAX_BATCH, ErrorCode::AccountCountMismatch);
+
+        let mut counts = vec![0u32; buckets.len() + 1];
+        for account_info in ctx.remaining_accounts.iter() {
+            let audit_proof = Account::<AuditProof>::try_from(account_info)?;
+
+            let bucket = buckets.partition_point(|boundary| *boundary <= audit_proof.risk_score);
+            counts[bucket] += 1;
+        }
+
+        emit!(RiskHistogram {
+            counts: counts.clone(),
+        });
+
+        Ok(counts)
+    }
+
+    pub fn get_proofs_summary<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReadProofs>,
+        start: u8,
+        limit: u8,
+    ) -> Result<()> {
+        require!(ctx.remaining_accounts.len() <= MAX_BATCH, ErrorCode::AccountCountMismatch);
+
+        let max_records = MAX_RETURN_DATA / PROOF_SUMMARY_LEN;
+        let proof_accounts = ctx
+            .remaining_accounts
+            .iter()
+            .skip(start as usize)
+            .take((limit as usize).min(max_records));
+
+        // Packed fixed-size records so clients can decode without Borsh
+        let mut return_data = Vec::with_capacity(max_records * PROOF_SUMMARY_LEN);
+        let mut count: u32 = 0;
+        for account_info in proof_accounts {
+            let audit_proof = Account::<AuditProof>::try_from(account_info)?;
+
+            return_data.extend_from_slice(audit_proof.contract_address.as_ref());
+            return_data.push(audit_proof.audit_score);
+            return_data.push(audit_proof.verified as u8);
+            count += 1;
+        }
+        set_return_data(&return_data);
+
+        emit!(SummaryBatch { count });
+
+        Ok(())
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_split_bps: u16,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        validate_bps(fee_split_bps)?;
+
+        config.admin = ctx.accounts.admin.key();
+        config.pending_admin = None;
+        config.fee_split_bps = fee_split_bps;
+        config.bump = ctx.bumps.config;
+
+        emit!(ConfigUpdated {
+            admin: config.admin,
+            update: ConfigUpdate {
+                fee_split_bps: Some(fee_split_bps),
+                ..Default::default()
+            },
+        });
+
+        Ok(())
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        update: ConfigUpdate,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // Once a timelock is configured, changes have to go through queue_action
+        require!(config.timelock_delay == 0, ErrorCode::TimelockRequired);
+        config.apply_update(&update)?;
+
+        emit!(ConfigUpdated {
+            admin: config.admin,
+            update,
+        });
+
+        Ok(())
+    }
+
+    pub fn get_config(ctx: Context<ReadConfig>) -> Result<()> {
+        let config = &ctx.accounts.config;
+
+        emit!(ConfigSnapshot {
+            admin: config.admin,
+            pending_admin: config.pending_admin,
+            fee_split_bps: config.fee_split_bps,
+            submission_fee: config.submission_fee,
+            min_oracle_stake: config.min_oracle_stake,
+            daily_reward_cap: config.daily_reward_cap,
+            rewards_frozen: config.rewards_frozen,
+            staking_pool: config.staking_pool,
+            treasury: config.treasury,
+            round_up_rewards: config.round_up_rewards,
+            audit_fee: config.audit_fee,
+            keeper_fee: config.keeper_fee,
+            claim_cooldown: config.claim_cooldown,
+            activity_bonus_bps: config.activity_bonus_bps,
+            verification_ttl: config.verification_ttl,
+            oracle_fee_bps: config.oracle_fee_bps,
+            max_submissions_per_day: config.max_submissions_per_day,
+            governance_bonus_bps: config.governance_bonus_bps,
+            max_bonus_votes: config.max_bonus_votes,
+            slash_distribution: config.slash_distribution,
+            decay_bps_per_day: config.decay_bps_per_day,
+            min_proposal_stake: config.min_proposal_stake,
+            reward_pool: config.reward_pool,
+            oracle_reward_source: config.oracle_reward_source,
+            delegation_cooldown: config.delegation_cooldown,
+            max_total_reward_multiple: config.max_total_reward_multiple,
+            pinning_pubkey: config.pinning_pubkey,
+            risk_bands: config.risk_bands,
+            badge_mint: config.badge_mint,
+            badge_bonus_bps: config.badge_bonus_bps,
+            timelock_delay: config.timelock_delay,
+            withdrawal_delay: config.withdrawal_delay,
+            consensus_window: config.consensus_window,
+            expiry_sweep_fee: config.expiry_sweep_fee,
+            max_vote_weight_bps: config.max_vote_weight_bps,
+            accepted_fee_mints: config.accepted_fee_mints.clone(),
+            min_tool_version: config.min_tool_version,
+            close_fee_bps: config.close_fee_bps,
+            manual_review_risk_threshold: config.manual_review_risk_threshold,
+            min_restake_principal: config.min_restake_principal,
+            max_oracle_reward: config.max_oracle_reward,
+        });
+
+        set_return_data(&config.try_to_vec()?);
+
+        Ok(())
+    }
+
+    pub fn freeze_rewards(
+        ctx: Context<UpdateConfig>,
+        frozen: bool,
+    ) -> Result<()> {
+        require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+        ctx.accounts.config.rewards_frozen = frozen;
+
+        emit!(RewardsFrozen { frozen });
+
+        Ok(())
+    }
+
+    // Schedule an admin action to run no earlier than timelock_delay from now
+    pub fn queue_action(
+        ctx: Context<QueueAction>,
+        action_type: u8,
+        params: Vec<u8>,
+    ) -> Result<()> {
+        // Decode now so malformed actions are rejected before the delay starts
+        AdminAction::decode(action_type, &params)?;
+
+        let config = &mut ctx.accounts.config;
+        let queued_action = &mut ctx.accounts.queued_action;
+        let now = current_time()?;
+
+        queued_action.action_id = config.queued_action_count;
+        queued_action.action_type = action_type;
+        queued_action.params = params;
+        queued_action.eta = now
+            .checked_add(config.timelock_delay as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        queued_action.bump = ctx.bumps.queued_action;
+        config.queued_action_count += 1;
+
+        emit!(ActionQueued {
+            action_id: queued_action.action_id,
+            action_type,
+            eta: queued_action.eta,
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+        let queued_action = &ctx.accounts.queued_action;
+        let config = &mut ctx.accounts.config;
+        let now = current_time()?;
+
+        require!(now >= queued_action.eta, ErrorCode::TimelockNotElapsed);
+
+        match AdminAction::decode(queued_action.action_type, &queued_action.params)? {
+            AdminAction::UpdateConfig(update) => {
+                config.apply_update(&update)?;
+
+                emit!(ConfigUpdated {
+                    admin: config.admin,
+                    update,
+                });
+            }
+            AdminAction::FreezeRewards(frozen) => {
+                config.rewards_frozen = frozen;
+
+                emit!(RewardsFrozen { frozen });
+            }
+        }
+
+        emit!(ActionExecuted {
+            action_id: queued_action.action_id,
+            action_type: queued_action.action_type,
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_action(ctx: Context<CancelAction>) -> Result<()> {
+        let queued_action = &ctx.accounts.queued_action;
+
+        emit!(ActionCancelled {
+            action_id: queued_action.action_id,
+            action_type: queued_action.action_type,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_oracle_registry(
+        ctx: Context<InitializeOracleRegistry>,
+        max_oracles: u16,
+    ) -> Result<()> {
+        require!(
+            max_oracles > 0 && max_oracles as usize <= MAX_ORACLES,
+            ErrorCode::InvalidRegistrySize
+        );
+
+        let registry = &mut ctx.accounts.oracle_registry;
+        registry.max_oracles = max_oracles;
+        registry.oracles = Vec::new();
+        registry.bump = ctx.bumps.oracle_registry;
+
+        Ok(())
+    }
+
+    // Register an oracle. A full registry rejects it unless `replace` is set, in which
+    // case the lowest-reputation oracle is evicted; the OracleReputation PDA of every
+    // registered oracle must then be passed as remaining accounts, in registry order
+    pub fn add_oracle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddOracle<'info>>,
+        oracle: Pubkey,
+        role: u8, // OracleRole
+        replace: bool,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+
+        OracleRole::try_from(role)?;
+        require!(registry.role_of(&oracle).is_none(), ErrorCode::OracleAlreadyRegistered);
+
+        let entry = OracleEntry { oracle, role };
+        if registry.oracles.len() < registry.max_oracles as usize {
+            registry.oracles.push(entry);
+
+            emit!(OracleAdded { oracle, role });
+
+            return Ok(());
+        }
+
+        require!(replace, ErrorCode::RegistryFull);
+        require!(
+            ctx.remaining_accounts.len() == registry.oracles.len(),
+            ErrorCode::AccountCountMismatch
+        );
+
+        // Reputation is lifetime submissions; ties evict the earliest registered oracle
+        let mut lowest: Option<(usize, u64)> = None;
+        for (index, account_info) in ctx.remaining_accounts.iter().enumerate() {
+            let registered = registry.oracles[index].oracle;
+            let submissions = if account_info.data_is_empty() {
+                // Never submitted; make sure this really is its reputation address
+                let (address, _) = Pubkey::find_program_address(
+                    &[b"oracle_reputation", registered.as_ref()],
+                    &crate::ID,
+                );
+                require_keys_eq!(account_info.key(), address, ErrorCode::Unauthorized);
+                0
+            } else {
+                let reputation = Account::<OracleReputation>::try_from(account_info)?;
+                require_keys_eq!(reputation.oracle, registered, ErrorCode::Unauthorized);
+                reputation.nonce
+            };
+
+            if lowest.map_or(true, |(_, fewest)| submissions < fewest) {
+                lowest = Some((index, submissions));
+            }
+        }
+
+        let (index, _) = lowest.ok_or(ErrorCode::RegistryFull)?;
+        let evicted = registry.oracles[index].oracle;
+        registry.oracles[index] = entry;
+
+        emit!(OracleEvicted {
+            evicted,
+            added: oracle,
+        });
+
+        Ok(())
+    }
+
+    pub fn propose_admin(
+        ctx: Context<UpdateConfig>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let now = current_time()?;
+
+        // The handover waits out the same delay as any other queued admin action
+        config.pending_admin = Some(new_admin);
+        config.pending_admin_eta = now
+            .checked_add(config.timelock_delay as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(AdminProposed {
+            admin: config.admin,
+            pending_admin: new_admin,
+            eta: config.pending_admin_eta,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let now = current_time()?;
+
+        require!(
+            config.pending_admin == Some(ctx.accounts.new_admin.key()),
+            ErrorCode::Unauthorized
+        );
+        require!(now >= config.pending_admin_eta, ErrorCode::TimelockNotElapsed);
+
+        let previous_admin = config.admin;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+        config.pending_admin_eta = 0;
+
+        emit!(AdminAccepted {
+            previous_admin,
+            admin: config.admin,
+        });
+
+        Ok(())
+    }
+
+    // Token economics, handled in token_economics.rs
+
+    pub fn initialize_token(ctx: Context<InitializeToken>, decimals: u8) -> Result<()> {
+        securizz_tokenomics::initialize_token(ctx, decimals)
+    }
+
+    pub fn initialize_staking_stats(ctx: Context<InitializeStakingStats>) -> Result<()> {
+        securizz_tokenomics::initialize_staking_stats(ctx)
+    }
+
+    pub fn stake_tokens(
+        ctx: Context<StakeTokens>,
+        amount: u64,
+        duration: u64,
+        grace_period: u64,
+        cliff_seconds: u64,
+    ) -> Result<()> {
+        securizz_tokenomics::stake_tokens(ctx, amount, duration, grace_period, cliff_seconds)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, min_amount: Option<u64>) -> Result<()> {
+        securizz_tokenomics::claim_rewards(ctx, min_amount)
+    }
+
+    pub fn claimable_preview(ctx: Context<PreviewClaim>) -> Result<()> {
+        securizz_tokenomics::claimable_preview(ctx)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        securizz_tokenomics::withdraw_stake(ctx)
+    }
+
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+        securizz_tokenomics::request_withdrawal(ctx, amount)
+    }
+
+    pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+        securizz_tokenomics::claim_withdrawal(ctx)
+    }
+
+    pub fn transfer_stake(ctx: Context<TransferStake>, new_owner: Pubkey) -> Result<()> {
+        securizz_tokenomics::transfer_stake(ctx, new_owner)
+    }
+
+    pub fn delegate_votes(ctx: Context<ChangeDelegation>, delegate: Pubkey) -> Result<()> {
+        securizz_tokenomics::delegate_votes(ctx, delegate)
+    }
+
+    pub fn revoke_delegation(ctx: Context<ChangeDelegation>) -> Result<()> {
+        securizz_tokenomics::revoke_delegation(ctx)
+    }
+
+    pub fn extend_lock(ctx: Context<ExtendLock>, new_duration: u64) -> Result<()> {
+        securizz_tokenomics::extend_lock(ctx, new_duration)
+    }
+
+    pub fn auto_restake(ctx: Context<AutoRestake>) -> Result<()> {
+        securizz_tokenomics::auto_restake(ctx)
+    }
+
+    pub fn oracle_reward(ctx: Context<OracleReward>, amount: u64) -> Result<()> {
+        securizz_tokenomics::oracle_reward(ctx, amount)
+    }
+
+    pub fn refill_reward_pool(ctx: Context<RefillRewardPool>, amount: u64) -> Result<()> {
+        securizz_tokenomics::refill_reward_pool(ctx, amount)
+    }
+
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+        securizz_tokenomics::slash_oracle(ctx, amount)
+    }
+
+    pub fn hold_stake(ctx: Context<SetStakeHold>, user: Pubkey) -> Result<()> {
+        securizz_tokenomics::hold_stake(ctx, user)
+    }
+
+    pub fn release_stake(ctx: Context<SetStakeHold>, user: Pubkey) -> Result<()> {
+        securizz_tokenomics::release_stake(ctx, user)
+    }
+
+    pub fn escrow_audit_payment(
+        ctx: Context<EscrowAuditPayment>,
+        amount: u64,
+        min_accepted_score: Option<u8>,
+        sla_score: Option<u8>,
+    ) -> Result<()> {
+        securizz_tokenomics::escrow_audit_payment(ctx, amount, min_accepted_score, sla_score)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        securizz_tokenomics::release_escrow(ctx)
+    }
+
+    pub fn sla_refund(ctx: Context<RefundSla>) -> Result<()> {
+        securizz_tokenomics::sla_refund(ctx)
+    }
+
+    pub fn pay_for_audit(ctx: Context<PayForAudit>, audit_fee: u64) -> Result<()> {
+        securizz_tokenomics::pay_for_audit(ctx, audit_fee)
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        voting_period: u64,
+        quorum: u64,
+        pass_threshold_bps: u16,
+    ) -> Result<()> {
+        securizz_tokenomics::create_proposal(ctx, proposal_id, voting_period, quorum, pass_threshold_bps)
+    }
+
+    pub fn vote_on_proposal(
+        ctx: Context<VoteOnProposal>,
+        proposal_id: u64,
+        vote_weight: u64,
+        vote_type: u8,
+    ) -> Result<()> {
+        securizz_tokenomics::vote_on_proposal(ctx, proposal_id, vote_weight, vote_type)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        securizz_tokenomics::finalize_proposal(ctx)
+    }
+
+    pub fn initialize_treasury_config(
+        ctx: Context<InitializeTreasuryConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        securizz_tokenomics::initialize_treasury_config(ctx, signers, threshold)
+    }
+
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        securizz_tokenomics::propose_withdrawal(ctx, amount, recipient)
+    }
+
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        securizz_tokenomics::approve_withdrawal(ctx)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(
+    contract_hash: [u8; 32],
+    report_hash: [u8; 32],
+    ipfs_cid: String,
+    risk_score: u64,
+    contract_address: Pubkey
+)]
+pub struct SubmitProof<'info> {
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + AuditProof::INIT_SPACE,
+        seeds = [b"audit_proof", contract_hash.as_ref(), oracle.key().as_ref()],
+        bump
+    )]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    #[account(mut)]
+    pub prior_proof: Option<Account<'info, AuditProof>>,
+    
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub oracle_stake: Option<Account<'info, StakeAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + OracleReputation::INIT_SPACE,
+        seeds = [b"oracle_reputation", oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_reputation: Account<'info, OracleReputation>,
+    
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + AddressIndex::INIT_SPACE,
+        seeds = [b"addr_index", contract_address.as_ref()],
+        bump
+    )]
+    pub address_index: Account<'info, AddressIndex>,
+    
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + AuditHistory::INIT_SPACE,
+        seeds = [b"audit_history", contract_address.as_ref()],
+        bump
+    )]
+    pub audit_history: Account<'info, AuditHistory>,
+    
+    #[account(
+        mut,
+        constraint = oracle_token_account.owner == oracle.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    /// CHECK: Instructions sysvar, only needed when a pin attestation is supplied
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    contract_hash: [u8; 32],
+    report_hash: [u8; 32],
+    ipfs_cid: String,
+    risk_score: u64,
+    contract_address: Pubkey,
+    audit_score: u8,
+    oracle_sig: [u8; 64],
+    oracle_pubkey: Pubkey
+)]
+pub struct SubmitProofAttested<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + AuditProof::INIT_SPACE,
+        seeds = [b"audit_proof", contract_hash.as_ref(), oracle_pubkey.as_ref()],
+        bump
+    )]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + OracleReputation::INIT_SPACE,
+        seeds = [b"oracle_reputation", oracle_pubkey.as_ref()],
+        bump
+    )]
+    pub oracle_reputation: Account<'info, OracleReputation>,
+    
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub oracle_stake: Option<Account<'info, StakeAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + AddressIndex::INIT_SPACE,
+        seeds = [b"addr_index", contract_address.as_ref()],
+        bump
+    )]
+    pub address_index: Account<'info, AddressIndex>,
+    
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + AuditHistory::INIT_SPACE,
+        seeds = [b"audit_history", contract_address.as_ref()],
+        bump
+    )]
+    pub audit_history: Account<'info, AuditHistory>,
+    
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == submitter.key() @ ErrorCode::Unauthorized
+    )]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the preceding ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVerification<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    #[account(
+        constraint = oracle_stake.user == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_stake: Account<'info, StakeAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"audit_history", audit_proof.contract_address.as_ref()],
+        bump = audit_history.bump
+    )]
+    pub audit_history: Option<Account<'info, AuditHistory>>,
+    
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProofField<'info> {
+    #[account(
+        mut,
+        has_one = oracle @ ErrorCode::Unauthorized,
+        constraint = !audit_proof.sealed @ ErrorCode::ProofSealed
+    )]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddTag<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(contract_hash: [u8; 32], oracle: Pubkey)]
+pub struct MigrateProof<'info> {
+    /// CHECK: May still use an older AuditProof layout; owner and address are
+    /// checked here and the data is deserialized after it has been resized
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"audit_proof", contract_hash.as_ref(), oracle.as_ref()],
+        bump
+    )]
+    pub audit_proof: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVerified<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + VerificationHistory::INIT_SPACE,
+        seeds = [b"verification_history", audit_proof.key().as_ref()],
+        bump
+    )]
+    pub verification_history: Account<'info, VerificationHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_history", audit_proof.contract_address.as_ref()],
+        bump = audit_history.bump
+    )]
+    pub audit_history: Option<Account<'info, AuditHistory>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeProof<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepExpired<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        mut,
+        address = config.treasury @ ErrorCode::Unauthorized,
+        constraint = treasury.owner == treasury_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == keeper.key() @ ErrorCode::Unauthorized
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the treasury authority PDA
+    #[account(
+        seeds = [b"treasury_authority"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub keeper: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProof<'info> {
+    #[account(mut, has_one = oracle @ ErrorCode::Unauthorized)]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    /// CHECK: May already be closed; only owner and size are inspected before it is loaded
+    #[account(
+        mut,
+        seeds = [b"addr_index", audit_proof.contract_address.as_ref()],
+        bump
+    )]
+    pub address_index: UncheckedAccount<'info>,
+
+    // Receives the fee as lamports; its token balance is untouched
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetProof<'info> {
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CompareProofs<'info> {
+    pub old_proof: Account<'info, AuditProof>,
+    #[account(
+        constraint = new_proof.contract_address == old_proof.contract_address @ ErrorCode::ContractMismatch
+    )]
+    pub new_proof: Account<'info, AuditProof>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAddress<'info> {
+    #[account(
+        seeds = [b"addr_index", address_index.contract_address.as_ref()],
+        bump = address_index.bump
+    )]
+    pub address_index: Account<'info, AddressIndex>,
+}
+
+#[derive(Accounts)]
+pub struct GetHistory<'info> {
+    #[account(
+        seeds = [b"audit_history", audit_history.contract_address.as_ref()],
+        bump = audit_history.bump
+    )]
+    pub audit_history: Account<'info, AuditHistory>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateSubmission {}
+
+#[derive(Accounts)]
+#[instruction(contract_hash: [u8; 32], oracle: Pubkey)]
+pub struct FindProof<'info> {
+    /// CHECK: May not exist yet; only its address, owner and size are inspected
+    #[account(
+        seeds = [b"audit_proof", contract_hash.as_ref(), oracle.as_ref()],
+        bump
+    )]
+    pub audit_proof: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckVerified<'info> {
+    #[account(
+        seeds = [b"audit_proof", audit_proof.contract_hash.as_ref(), audit_proof.oracle.as_ref()],
+        bump = audit_proof.bump
+    )]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CheckVerifiedByAddress<'info> {
+    #[account(
+        seeds = [b"addr_index", address_index.contract_address.as_ref()],
+        bump = address_index.bump
+    )]
+    pub address_index: Account<'info, AddressIndex>,
+
+    #[account(
+        seeds = [b"audit_proof", address_index.contract_hash.as_ref(), address_index.oracle.as_ref()],
+        bump = audit_proof.bump
+    )]
+    pub audit_proof: Account<'info, AuditProof>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyIntegrity<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+    
+    #[account(
+        constraint = oracle_stake.user == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_stake: Account<'info, StakeAccount>,
+    
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyBatch<'info> {
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        constraint = oracle_stake.user == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_stake: Account<'info, StakeAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+// Proofs are passed through remaining_accounts
+#[derive(Accounts)]
+pub struct ReadProofs {}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReadConfig<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedAction::INIT_SPACE,
+        seeds = [b"queued_action", config.queued_action_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"queued_action", queued_action.action_id.to_le_bytes().as_ref()],
+        bump = queued_action.bump,
+        close = admin
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"queued_action", queued_action.action_id.to_le_bytes().as_ref()],
+        bump = queued_action.bump,
+        close = admin
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + OracleRegistry::INIT_SPACE,
+        seeds = [b"oracle_registry"],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // set by propose_admin, cleared on accept
+    pub fee_split_bps: u16, // share of audit fees routed to stakers
+    pub submission_fee: u64, // charged to oracles below min_oracle_stake
+    pub min_oracle_stake: u64,
+    pub daily_reward_cap: u64, // max rewards per stake per day, 0 = uncapped
+    pub rewards_frozen: bool, // halts reward claims, principal stays withdrawable
+    pub staking_pool: Pubkey,
+    pub treasury: Pubkey,
+    // Ceiling instead of floor division for accrued rewards. Stakers stop losing
+    // dust to truncation, at the cost of the pool paying up to one base unit more
+    // per stake over its lifetime.
+    pub round_up_rewards: bool,
+    pub audit_fee: u64, // required fee per audit, 0 = payer chooses the amount
+    pub keeper_fee: u64, // max paid to auto_restake callers, out of the restaked stake's rewards
+    pub claim_cooldown: u64, // minimum seconds between reward claims
+    pub activity_bonus_bps: u16, // share of a stake's pro-rata cut of pool audit fees paid as a bonus, 0 = off
+    pub verification_ttl: u64, // seconds a verification stays fresh, 0 = forever
+    pub oracle_fee_bps: u16, // oracle's share of released escrow, rest to treasury
+    pub max_submissions_per_day: u16, // per oracle, 0 = unlimited
+    pub governance_bonus_bps: u16, // bonus of principal per governance vote during a stake, 0 = off
+    pub max_bonus_votes: u64, // votes beyond this earn no further governance bonus
+    pub slash_distribution: SlashDistribution, // where slashed oracle stake goes
+    pub decay_bps_per_day: u16, // audit score lost per day since submission, 0 = no decay
+    pub min_proposal_stake: u64, // bonded by proposers until their proposal finalizes
+    pub reward_pool: Pubkey, // pays rewards and keeper fees; principal stays in staking_pool
+    pub oracle_reward_source: RewardSource, // whether oracle rewards are minted or paid from the treasury
+    pub delegation_cooldown: u64, // seconds a stake must wait between delegation changes
+    pub max_total_reward_multiple: u16, // lifetime rewards cap as a percent of principal (200 = 2x), 0 = uncapped
+    pub pinning_pubkey: Pubkey, // pinning service that signs CIDs it has pinned
+    pub risk_bands: RiskBands, // oracle signatures required to verify, by risk_score
+    pub badge_mint: Pubkey, // auditor badge NFT whose holders earn badge_bonus_bps
+    pub badge_bonus_bps: u16, // extra rewards on each claim for badge holders
+    pub queued_action_count: u64, // next QueuedAction id
+    pub timelock_delay: u64, // seconds between queue_action and execute_action, 0 = changes apply directly
+    pub withdrawal_delay: u64, // seconds between request_withdrawal and claim_withdrawal
+    pub consensus_window: u64, // max seconds from first to quorum-completing signature, 0 = unbounded
+    pub expiry_sweep_fee: u64, // paid from the treasury to sweep_expired callers
+    pub max_vote_weight_bps: u16, // cap on a single vote as a share of total staked, 0 = uncapped
+    #[max_len(MAX_FEE_MINTS)]
+    pub accepted_fee_mints: Vec<Pubkey>, // mints pay_for_audit accepts, empty = reward pool mint only
+    pub min_tool_version: u32, // oldest analyzer version accepted at submission, packed like tool_version
+    pub close_fee_bps: u16, // treasury's cut of rent reclaimed by close_proof
+    pub manual_review_risk_threshold: u64, // automated-only proofs above this risk cannot be verified, 0 = off
+    pub last_known_time: i64, // unused since current_time fails closed, kept so later fields keep their offsets
+    pub min_restake_principal: u64, // smallest stake auto_restake will re-lock
+    pub max_oracle_reward: u64, // largest oracle reward paid from the treasury, 0 = none
+    pub pending_admin_eta: i64, // earliest time pending_admin may accept
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct ConfigUpdate {
+    pub fee_split_bps: Option<u16>,
+    pub submission_fee: Option<u64>,
+    pub min_oracle_stake: Option<u64>,
+    pub daily_reward_cap: Option<u64>,
+    pub staking_pool: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub round_up_rewards: Option<bool>,
+    pub audit_fee: Option<u64>,
+    pub keeper_fee: Option<u64>,
+    pub claim_cooldown: Option<u64>,
+    pub activity_bonus_bps: Option<u16>,
+    pub verification_ttl: Option<u64>,
+    pub oracle_fee_bps: Option<u16>,
+    pub max_submissions_per_day: Option<u16>,
+    pub governance_bonus_bps: Option<u16>,
+    pub max_bonus_votes: Option<u64>,
+    pub slash_distribution: Option<SlashDistribution>,
+    pub decay_bps_per_day: Option<u16>,
+    pub min_proposal_stake: Option<u64>,
+    pub reward_pool: Option<Pubkey>,
+    pub oracle_reward_source: Option<RewardSource>,
+    pub delegation_cooldown: Option<u64>,
+    pub max_total_reward_multiple: Option<u16>,
+    pub pinning_pubkey: Option<Pubkey>,
+    pub risk_bands: Option<RiskBands>,
+    pub badge_mint: Option<Pubkey>,
+    pub badge_bonus_bps: Option<u16>,
+    pub timelock_delay: Option<u64>,
+    pub withdrawal_delay: Option<u64>,
+    pub consensus_window: Option<u64>,
+    pub expiry_sweep_fee: Option<u64>,
+    pub max_vote_weight_bps: Option<u16>,
+    #[max_len(MAX_FEE_MINTS)]
+    pub accepted_fee_mints: Option<Vec<Pubkey>>,
+    pub min_tool_version: Option<u32>,
+    pub close_fee_bps: Option<u16>,
+    pub manual_review_risk_threshold: Option<u64>,
+    pub min_restake_principal: Option<u64>,
+    pub max_oracle_reward: Option<u64>,
+}
+
+impl ProgramConfig {
+    // With no accepted mints configured, only the reward pool's mint is accepted
+    pub fn accepts_fee_mint(&self, mint: &Pubkey, reward_mint: &Pubkey) -> bool {
+        if self.accepted_fee_mints.is_empty() {
+            mint == reward_mint
+        } else {
+            self.accepted_fee_mints.contains(mint)
+        }
+    }
+
+    pub fn apply_update(&mut self, update: &ConfigUpdate) -> Result<()> {
+        if let Some(fee_split_bps) = update.fee_split_bps {
+            validate_bps(fee_split_bps)?;
+            self.fee_split_bps = fee_split_bps;
+        }
+        if let Some(submission_fee) = update.submission_fee {
+            self.submission_fee = submission_fee;
+        }
+        if let Some(min_oracle_stake) = update.min_oracle_stake {
+            self.min_oracle_stake = min_oracle_stake;
+        }
+        if let Some(daily_reward_cap) = update.daily_reward_cap {
+            self.daily_reward_cap = daily_reward_cap;
+        }
+        if let Some(staking_pool) = update.staking_pool {
+            self.staking_pool = staking_pool;
+        }
+        if let Some(treasury) = update.treasury {
+            self.treasury = treasury;
+        }
+        if let Some(round_up_rewards) = update.round_up_rewards {
+            self.round_up_rewards = round_up_rewards;
+        }
+        if let Some(audit_fee) = update.audit_fee {
+            self.audit_fee = audit_fee;
+        }
+        if let Some(keeper_fee) = update.keeper_fee {
+            self.keeper_fee = keeper_fee;
+        }
+        if let Some(claim_cooldown) = update.claim_cooldown {
+            self.claim_cooldown = claim_cooldown;
+        }
+        if let Some(activity_bonus_bps) = update.activity_bonus_bps {
+            validate_bps(activity_bonus_bps)?;
+            self.activity_bonus_bps = activity_bonus_bps;
+        }
+        if let Some(verification_ttl) = update.verification_ttl {
+            self.verification_ttl = verification_ttl;
+        }
+        if let Some(oracle_fee_bps) = update.oracle_fee_bps {
+            validate_bps(oracle_fee_bps)?;
+            self.oracle_fee_bps = oracle_fee_bps;
+        }
+        if let Some(max_submissions_per_day) = update.max_submissions_per_day {
+            self.max_submissions_per_day = max_submissions_per_day;
+        }
+        if let Some(governance_bonus_bps) = update.governance_bonus_bps {
+            validate_bps(governance_bonus_bps)?;
+            self.governance_bonus_bps = governance_bonus_bps;
+        }
+        if let Some(max_bonus_votes) = update.max_bonus_votes {
+            self.max_bonus_votes = max_bonus_votes;
+        }
+        if let Some(slash_distribution) = update.slash_distribution {
+            slash_distribution.validate()?;
+            self.slash_distribution = slash_distribution;
+        }
+        if let Some(decay_bps_per_day) = update.decay_bps_per_day {
+            validate_bps(decay_bps_per_day)?;
+            self.decay_bps_per_day = decay_bps_per_day;
+        }
+        if let Some(min_proposal_stake) = update.min_proposal_stake {
+            self.min_proposal_stake = min_proposal_stake;
+        }
+        if let Some(reward_pool) = update.reward_pool {
+            self.reward_pool = reward_pool;
+        }
+        if let Some(oracle_reward_source) = update.oracle_reward_source {
+            self.oracle_reward_source = oracle_reward_source;
+        }
+        if let Some(delegation_cooldown) = update.delegation_cooldown {
+            self.delegation_cooldown = delegation_cooldown;
+        }
+        if let Some(max_total_reward_multiple) = update.max_total_reward_multiple {
+            self.max_total_reward_multiple = max_total_reward_multiple;
+        }
+        if let Some(pinning_pubkey) = update.pinning_pubkey {
+            self.pinning_pubkey = pinning_pubkey;
+        }
+        if let Some(risk_bands) = update.risk_bands {
+            risk_bands.validate()?;
+            self.risk_bands = risk_bands;
+        }
+        if let Some(badge_mint) = update.badge_mint {
+            self.badge_mint = badge_mint;
+        }
+        if let Some(badge_bonus_bps) = update.badge_bonus_bps {
+            validate_bps(badge_bonus_bps)?;
+            self.badge_bonus_bps = badge_bonus_bps;
+        }
+        if let Some(timelock_delay) = update.timelock_delay {
+            self.timelock_delay = timelock_delay;
+        }
+        if let Some(withdrawal_delay) = update.withdrawal_delay {
+            self.withdrawal_delay = withdrawal_delay;
+        }
+        if let Some(consensus_window) = update.consensus_window {
+            self.consensus_window = consensus_window;
+        }
+        if let Some(expiry_sweep_fee) = update.expiry_sweep_fee {
+            self.expiry_sweep_fee = expiry_sweep_fee;
+        }
+        if let Some(max_vote_weight_bps) = update.max_vote_weight_bps {
+            validate_bps(max_vote_weight_bps)?;
+            self.max_vote_weight_bps = max_vote_weight_bps;
+        }
+        if let Some(accepted_fee_mints) = &update.accepted_fee_mints {
+            require!(accepted_fee_mints.len() <= MAX_FEE_MINTS, ErrorCode::TooManyFeeMints);
+            self.accepted_fee_mints = accepted_fee_mints.clone();
+        }
+        if let Some(min_tool_version) = update.min_tool_version {
+            self.min_tool_version = min_tool_version;
+        }
+        if let Some(close_fee_bps) = update.close_fee_bps {
+            validate_bps(close_fee_bps)?;
+            self.close_fee_bps = close_fee_bps;
+        }
+        if let Some(manual_review_risk_threshold) = update.manual_review_risk_threshold {
+            self.manual_review_risk_threshold = manual_review_risk_threshold;
+        }
+        if let Some(min_restake_principal) = update.min_restake_principal {
+            self.min_restake_principal = min_restake_principal;
+        }
+        if let Some(max_oracle_reward) = update.max_oracle_reward {
+            self.max_oracle_reward = max_oracle_reward;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum RewardSource {
+    #[default]
+    Mint,
+    Treasury,
+}
+
+// Admin action waiting out the timelock; closed when executed or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedAction {
+    pub action_id: u64,
+    pub action_type: u8, // AdminAction discriminant
+    #[max_len(MAX_ACTION_PARAMS)]
+    pub params: Vec<u8>, // borsh-encoded arguments for the action
+    pub eta: i64,
+    pub bump: u8,
+}
+
+// Admin actions that can be queued behind the timelock
+pub enum AdminAction {
+    UpdateConfig(ConfigUpdate), // action_type 0
+    FreezeRewards(bool),        // action_type 1
+}
+
+impl AdminAction {
+    pub fn decode(action_type: u8, params: &[u8]) -> Result<Self> {
+        let action = match action_type {
+            0 => ConfigUpdate::try_from_slice(params).map(AdminAction::UpdateConfig),
+            1 => bool::try_from_slice(params).map(AdminAction::FreezeRewards),
+            _ => return err!(ErrorCode::InvalidActionType),
+        };
+        action.map_err(|_| error!(ErrorCode::InvalidActionParams))
+    }
+}
+
+// Oracle signatures update_verification needs before a proof counts as verified.
+// Bands are inclusive lower bounds on risk_score; an unset config needs one signature
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RiskBands {
+    pub medium_risk_from: u64,
+    pub high_risk_from: u64,
+    pub low_signatures: u8,
+    pub medium_signatures: u8,
+    pub high_signatures: u8,
+}
+
+impl RiskBands {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.medium_risk_from <= self.high_risk_from, ErrorCode::InvalidRiskBands);
+        for signatures in [self.low_signatures, self.medium_signatures, self.high_signatures] {
+            require!(signatures as usize <= MAX_VERIFIERS, ErrorCode::InvalidRiskBands);
+        }
+        Ok(())
+    }
+
+    pub fn required_signatures(&self, risk_score: u64) -> u8 {
+        let signatures = if self.high_risk_from > 0 && risk_score >= self.high_risk_from {
+            self.high_signatures
+        } else if self.medium_risk_from > 0 && risk_score >= self.medium_risk_from {
+            self.medium_signatures
+        } else {
+            self.low_signatures
+        };
+        signatures.max(1)
+    }
+}
+
+// Split of slashed stake; whatever is not burned or kept for rewards goes to
+// the treasury, so an unset distribution sends everything there
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SlashDistribution {
+    pub burn_bps: u16,
+    pub treasury_bps: u16,
+    pub rewards_bps: u16, // left in the staking pool as reward reserve
+}
+
+impl SlashDistribution {
+    pub fn validate(&self) -> Result<()> {
+        let total = self.burn_bps as u32 + self.treasury_bps as u32 + self.rewards_bps as u32;
+        require!(total == 10_000, ErrorCode::InvalidSlashDistribution);
+        Ok(())
+    }
+
+    // (burned, to_treasury, to_rewards)
+    pub fn split(&self, amount: u64) -> (u64, u64, u64) {
+        let burned = apply_bps(amount, self.burn_bps);
+        let to_rewards = apply_bps(amount, self.rewards_bps);
+        (burned, amount - burned - to_rewards, to_rewards)
+    }
+}
+
+// Latest proof submitted for a deployed contract address
+#[account]
+#[derive(InitSpace)]
+pub struct AddressIndex {
+    pub contract_address: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub oracle: Pubkey, // with contract_hash, locates the proof PDA
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+// Audit timeline for a deployed contract address, oldest first
+#[account]
+#[derive(InitSpace)]
+pub struct AuditHistory {
+    pub contract_address: Pubkey,
+    #[max_len(MAX_AUDIT_HISTORY)]
+    pub entries: Vec<HistoryEntry>,
+    pub bump: u8,
+}
+
+impl AuditHistory {
+    // Appends the proof's current state, evicting the oldest entry once full
+    pub fn record(&mut self, proof: &AuditProof, now: i64) {
+        if self.entries.len() >= MAX_AUDIT_HISTORY {
+            self.entries.remove(0);
+        }
+        self.entries.push(HistoryEntry {
+            contract_hash: proof.contract_hash,
+            audit_score: proof.audit_score,
+            timestamp: now,
+            verified: proof.verified,
+        });
+
+        emit!(HistoryAppended {
+            contract_address: self.contract_address,
+            contract_hash: proof.contract_hash,
+            verified: proof.verified,
+            entries: self.entries.len() as u8,
+        });
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HistoryEntry {
+    pub contract_hash: [u8; 32],
+    pub audit_score: u8,
+    pub timestamp: i64,
+    pub verified: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OracleRegistry {
+    pub max_oracles: u16, // at most MAX_ORACLES
+    #[max_len(MAX_ORACLES)]
+    pub oracles: Vec<OracleEntry>,
+    pub bump: u8,
+}
+
+impl OracleRegistry {
+    // None for unregistered oracles
+    pub fn role_of(&self, oracle: &Pubkey) -> Option<OracleRole> {
+        self.oracles
+            .iter()
+            .find(|entry| entry.oracle == *oracle)
+            .and_then(|entry| OracleRole::try_from(entry.role).ok())
+    }
+
+    pub fn can_submit(&self, oracle: &Pubkey) -> bool {
+        matches!(self.role_of(oracle), Some(OracleRole::Submitter | OracleRole::Both))
+    }
+
+    pub fn can_verify(&self, oracle: &Pubkey) -> bool {
+        matches!(self.role_of(oracle), Some(OracleRole::Verifier | OracleRole::Both))
+    }
+
+    // Separation of duties applies once any oracle is limited to a single role
+    pub fn roles_split(&self) -> bool {
+        self.oracles.iter().any(|entry| entry.role != OracleRole::Both as u8)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct OracleEntry {
+    pub oracle: Pubkey,
+    pub role: u8, // OracleRole
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuditMethod {
+    Automated,
+    Manual,
+    Hybrid,
+}
+
+impl TryFrom<u8> for AuditMethod {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(AuditMethod::Automated),
+            1 => Ok(AuditMethod::Manual),
+            2 => Ok(AuditMethod::Hybrid),
+            _ => err!(ErrorCode::InvalidAuditMethod),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleRole {
+    Submitter,
+    Verifier,
+    Both,
+}
+
+impl TryFrom<u8> for OracleRole {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(OracleRole::Submitter),
+            1 => Ok(OracleRole::Verifier),
+            2 => Ok(OracleRole::Both),
+            _ => err!(ErrorCode::InvalidOracleRole),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OracleReputation {
+    pub oracle: Pubkey,
+    pub last_submission_time: i64,
+    pub submissions_today: u16,
+    pub nonce: u64, // next expected submission nonce
+    pub bump: u8,
+}
+
+impl OracleReputation {
+    // Counts a submission against the daily limit, starting a fresh count on a new day
+    pub fn record_submission(&mut self, now: i64, max_per_day: u16) -> Result<()> {
+        let day = SECONDS_PER_DAY as i64;
+        if now / day != self.last_submission_time / day {
+            self.submissions_today = 0;
+        }
+
+        require!(
+            max_per_day == 0 || self.submissions_today < max_per_day,
+            ErrorCode::RateLimitExceeded
+        );
+
+        self.submissions_today += 1;
+        self.last_submission_time = now;
+
+        Ok(())
+    }
+}
+
+// Admin overrides of a proof's verified flag, oldest first
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationHistory {
+    pub audit_proof: Pubkey,
+    #[max_len(MAX_VERIFICATION_HISTORY)]
+    pub entries: Vec<VerificationChange>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct VerificationChange {
+    pub verified: bool,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+    #[max_len(MAX_CID_LEN)]
+    pub reason_cid: String,
+}
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct AuditProof {
+    pub contract_hash: [u8; 32],
+    pub report_hash: [u8; 32],
+    #[max_len(MAX_CID_LEN)]
+    pub ipfs_cid: String,
+    pub contract_address: Pubkey,
+    pub audit_score: u8, // 0-100 score
+    pub risk_score: u64,
+    pub timestamp: i64,
+    pub verification_timestamp: i64,
+    pub verified: bool,
+    pub oracle: Pubkey,
+    pub supersedes: Option<[u8; 32]>, // contract hash of the prior audit
+    pub superseded_by: Option<[u8; 32]>,
+    #[max_len(MAX_TAGS)]
+    pub tags: Vec<ProofTag>,
+    pub commitment: [u8; 32], // see compute_commitment
+    // Fields added after this one must be appended and zero-defaulted so that
+    // migrate_proof can extend older accounts in place
+    pub layout_version: u8,
+    pub revoked: bool,
+    pub findings: FindingCategories,
+    pub content_hash: [u8; 32],
+    pub expiry_timestamp: i64, // verification lapses after this, 0 = never
+    pub bump: u8,
+    pub pin_attestation: Option<[u8; 64]>, // pinning service signature over ipfs_cid
+    #[max_len(MAX_VERIFIERS)]
+    pub verifier_signatures: Vec<Pubkey>, // oracles that signed off via update_verification
+    pub version: u32, // starts at 1, bumped by each field setter
+    pub sealed: bool, // set by seal_proof; fields can no longer change
+    #[max_len(MAX_REPORTS)]
+    pub report_cids: Vec<ReportEntry>, // summary, detailed and appendix reports alongside ipfs_cid
+    pub first_signature_time: i64, // when the current round of verifier signatures began
+    pub expired: bool, // set by sweep_expired once expiry_timestamp has passed
+    pub fee_mint: Pubkey, // mint the audit fee was paid in, default until paid
+    pub tool_version: u32, // analyzer semver packed as major << 16 | minor << 8 | patch
+    pub upgrade_authority: Option<Pubkey>, // audited program's upgrade authority, None = immutable
+    pub audit_method: u8, // AuditMethod
+    #[max_len(MAX_ALLOWED_VERIFIERS)]
+    pub allowed_verifiers: Vec<Pubkey>, // private audits, only these oracles may verify; empty = any
+    pub oracle_rewarded: bool, // set once oracle_reward has paid for this proof
+    pub hash_algo: u8, // HashAlgo used for contract_hash and report_hash
+}
+
+impl AuditProof {
+    // Edited proofs need fresh verification. The commitment is left as submitted
+    // so check_commitment catches the edit until seal_proof commits to it
+    pub fn field_updated(&mut self) -> Result<()> {
+        self.verified = false;
+        self.verifier_signatures.clear();
+        self.first_signature_time = 0;
+        self.version = self.version.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Private proofs restrict verification to their allowlist
+    pub fn verifier_allowed(&self, oracle: &Pubkey) -> bool {
+        self.allowed_verifiers.is_empty() || self.allowed_verifiers.contains(oracle)
+    }
+
+    // Proofs riskier than the configured threshold need a human in the loop to verify
+    pub fn check_review_method(&self, config: &ProgramConfig) -> Result<()> {
+        let threshold = config.manual_review_risk_threshold;
+        if threshold > 0 && self.risk_score > threshold {
+            require!(
+                self.audit_method != AuditMethod::Automated as u8,
+                ErrorCode::ManualReviewRequired
+            );
+        }
+        Ok(())
+    }
+
+    // Marks the proof verified at `now`, expiring `ttl` seconds later (0 = never)
+    pub fn mark_verified(&mut self, now: i64, ttl: u64) {
+        self.verified = true;
+        self.expired = false;
+        self.verification_timestamp = now;
+        self.expiry_timestamp = if ttl > 0 { now.saturating_add(ttl as i64) } else { 0 };
+    }
+
+    // Commitment over the report fields using the proof's hash algorithm
+    // hash(ipfs_cid || risk_score || audit_score) until sealed, then
+    // hash(contract_hash || report_hash || ipfs_cid || audit_score || risk_score)
+    pub fn compute_commitment(&self) -> Result<[u8; 32]> {
+        let risk_score = self.risk_score.to_le_bytes();
+        let audit_score = [self.audit_score];
+        let parts: &[&[u8]] = if self.sealed {
+            &[
+                &self.contract_hash,
+                &self.report_hash,
+                self.ipfs_cid.as_bytes(),
+                &audit_score,
+                &risk_score,
+            ]
+        } else {
+            &[self.ipfs_cid.as_bytes(), &risk_score, &audit_score]
+        };
+
+        Ok(match HashAlgo::try_from(self.hash_algo)? {
+            HashAlgo::Sha256 => hash::hashv(parts).to_bytes(),
+            HashAlgo::Keccak256 => keccak::hashv(parts).to_bytes(),
+            HashAlgo::Blake3 => blake3::hashv(parts).to_bytes(),
+        })
+    }
+
+    // Verified, not revoked, and neither swept nor past its expiry timestamp
+    pub fn is_currently_verified(&self, now: i64) -> bool {
+        self.verified
+            && !self.revoked
+            && !self.expired
+            && (self.expiry_timestamp == 0 || now <= self.expiry_timestamp)
+    }
+
+    // Verified longer than `ttl` seconds ago; a ttl of 0 never goes stale
+    pub fn is_stale(&self, now: i64, ttl: u64) -> bool {
+        self.verified && ttl > 0 && now > self.verification_timestamp.saturating_add(ttl as i64)
+    }
+
+    // audit_score reduced linearly by `decay_bps_per_day` for each whole day since
+    // submission, clamped at 0; the stored score is never modified
+    pub fn effective_score(&self, now: i64, decay_bps_per_day: u16) -> u8 {
+        let days = now.saturating_sub(self.timestamp).max(0) as u64 / SECONDS_PER_DAY;
+        let decay_bps = days.saturating_mul(decay_bps_per_day as u64).min(10_000);
+        let remaining_bps = 10_000 - decay_bps;
+        (self.audit_score as u64 * remaining_bps / 10_000) as u8
+    }
+
+    // Bytes an off-chain oracle signs to attest to a proof as its `nonce`th submission
+    pub fn attestation_message(&self, nonce: u64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 + 32 + 4 + self.ipfs_cid.len() + 8 + 32 + 1 + 32 + 4 + 33 + 1 + 8);
+        message.extend_from_slice(&self.contract_hash);
+        message.extend_from_slice(&self.report_hash);
+        message.extend_from_slice(&(self.ipfs_cid.len() as u32).to_le_bytes());
+        message.extend_from_slice(self.ipfs_cid.as_bytes());
+        message.extend_from_slice(&self.risk_score.to_le_bytes());
+        message.extend_from_slice(self.contract_address.as_ref());
+        message.push(self.audit_score);
+        message.extend_from_slice(&self.content_hash);
+        message.extend_from_slice(&self.tool_version.to_le_bytes());
+        match self.upgrade_authority {
+            Some(authority) => {
+                message.push(1);
+                message.extend_from_slice(authority.as_ref());
+            }
+            None => message.push(0),
+        }
+        message.push(self.audit_method);
+        message.extend_from_slice(&nonce.to_le_bytes());
+        message
+    }
+}
+
+// Reads the clock, failing closed with ClockUnavailable when the sysvar can't be
+// read. A recorded fallback time could be arbitrarily stale and let expired
+// proofs, closed votes and lapsed timelocks pass
+pub fn current_time() -> Result<i64> {
+    Clock::get()
+        .map(|clock| clock.unix_timestamp)
+        .map_err(|_| error!(ErrorCode::ClockUnavailable))
+}
+
+// Every CID stored on-chain is reserved MAX_CID_LEN bytes of account space
+pub fn validate_cid_len(cid: &str) -> Result<()> {
+    require!(cid.len() <= MAX_CID_LEN, ErrorCode::InvalidIpfsCid);
+    Ok(())
+}
+
+// First submitted field that breaks the proof rules, with the error submit_proof raises for it
+pub fn invalid_submission_field(
+    audit_score: u8,
+    risk_score: u64,
+    ipfs_cid: &str,
+) -> Option<(&'static str, ErrorCode)> {
+    if audit_score > MAX_AUDIT_SCORE {
+        return Some(("audit_score", ErrorCode::InvalidAuditScore));
+    }
+    if risk_score > MAX_RISK_SCORE {
+        return Some(("risk_score", ErrorCode::InvalidRiskScore));
+    }
+    if ipfs_cid.is_empty() || validate_cid_len(ipfs_cid).is_err() {
+        return Some(("ipfs_cid", ErrorCode::InvalidIpfsCid));
+    }
+    None
+}
+
+/// Fails unless `proof` is verified, not revoked and not past its expiry.
+///
+/// Intended for other Anchor programs that must only interact with audited
+/// contracts, typically before acting on a proof account they were passed:
+///
+/// ```ignore
+/// use securizz::{assert_verified_proof, AuditProof};
+///
+/// let proof: &AuditProof = &ctx.accounts.audit_proof;
+/// assert_verified_proof(proof, Clock::get()?.unix_timestamp)?;
+/// ```
+pub fn assert_verified_proof(proof: &AuditProof, now: i64) -> Result<()> {
+    require!(proof.verified, ErrorCode::ProofNotVerified);
+    require!(!proof.revoked, ErrorCode::ProofRevoked);
+    require!(proof.is_currently_verified(now), ErrorCode::ProofExpired);
+    Ok(())
+}
+
+// Shared by the proof- and address-keyed lookups; a superseded proof no longer
+// describes the deployed contract
+fn check_verified_proof(audit_proof: &AuditProof, config: &ProgramConfig, now: i64) -> Result<bool> {
+    let ok = audit_proof.is_currently_verified(now) && audit_proof.superseded_by.is_none();
+    let stale = audit_proof.is_stale(now, config.verification_ttl);
+
+    emit!(VerifiedCheck {
+        contract_address: audit_proof.contract_address,
+        ok,
+        stale,
+    });
+
+    Ok(ok)
+}
+
+// Records `signer`'s signature and marks the proof verified once its risk band's
+// quorum is met. Every oracle verification path goes through here; callers check
+// the signer's stake. Returns whether this signature completed the quorum
+fn add_verifier_signature(
+    audit_proof: &mut AuditProof,
+    signer: Pubkey,
+    registry: &OracleRegistry,
+    config: &ProgramConfig,
+    now: i64,
+) -> Result<bool> {
+    require!(registry.can_verify(&signer), ErrorCode::Unauthorized);
+    require!(audit_proof.verifier_allowed(&signer), ErrorCode::Unauthorized);
+    require!(!audit_proof.verifier_signatures.contains(&signer), ErrorCode::AlreadySigned);
+    require!(audit_proof.verifier_signatures.len() < MAX_VERIFIERS, ErrorCode::TooManyVerifiers);
+    if audit_proof.verifier_signatures.is_empty() {
+        audit_proof.first_signature_time = now;
+    }
+    audit_proof.verifier_signatures.push(signer);
+
+    // The quorum follows the proof's current risk band, not the config at submit time
+    let required_signatures = config.risk_bands.required_signatures(audit_proof.risk_score);
+
+    // Emitted for every new signature, including the one that completes the quorum
+    emit!(SignatureProgress {
+        contract_hash: audit_proof.contract_hash,
+        current: audit_proof.verifier_signatures.len() as u8,
+        required: required_signatures,
+    });
+
+    if audit_proof.verified || audit_proof.verifier_signatures.len() < required_signatures as usize {
+        return Ok(false);
+    }
+
+    // A quorum assembled over too long a span is stale; the oracle must restart it
+    let window = config.consensus_window;
+    require!(
+        window == 0 || now.saturating_sub(audit_proof.first_signature_time) as u64 <= window,
+        ErrorCode::ConsensusExpired
+    );
+    audit_proof.check_review_method(config)?;
+    // With duties split, someone besides the submitter must have signed
+    if registry.roles_split() {
+        require!(
+            audit_proof.verifier_signatures.iter().any(|signer| *signer != audit_proof.oracle),
+            ErrorCode::Unauthorized
+        );
+    }
+    audit_proof.mark_verified(now, config.verification_ttl);
+
+    Ok(true)
+}
+
+// Anti-spam: the oracle must either hold the minimum stake or have the submission
+// fee paid into the treasury from `fee_account`, signed for by `fee_authority`
+fn enforce_submission_requirement<'info>(
+    config: &ProgramConfig,
+    oracle: Pubkey,
+    oracle_stake: Option<&Account<'info, StakeAccount>>,
+    fee_account: Option<&Account<'info, TokenAccount>>,
+    treasury: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    fee_authority: AccountInfo<'info>,
+) -> Result<()> {
+    if config.submission_fee == 0 && config.min_oracle_stake == 0 {
+        return Ok(());
+    }
+
+    let meets_stake = match oracle_stake {
+        Some(stake) => {
+            config.min_oracle_stake > 0
+                && stake.user == oracle
+                && stake.amount >= config.min_oracle_stake
+        }
+        None => false,
+    };
+    if meets_stake {
+        return Ok(());
+    }
+
+    require!(config.submission_fee > 0, ErrorCode::SubmissionRequirementNotMet);
+    let (fee_account, treasury, token_program) = match (fee_account, treasury, token_program) {
+        (Some(from), Some(to), Some(program)) => (from, to, program),
+        _ => return err!(ErrorCode::SubmissionRequirementNotMet),
+    };
+
+    let cpi_accounts = Transfer {
+        from: fee_account.to_account_info(),
+        to: treasury.to_account_info(),
+        authority: fee_authority,
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, config.submission_fee)?;
+
+    emit!(SubmissionFeePaid {
+        oracle,
+        amount: config.submission_fee,
+    });
+
+    Ok(())
+}
+
+// Checks that `ix` is an ed25519 program instruction verifying exactly one
+// signature by `pubkey` over `message`, with all data inline in that instruction
+pub fn verify_ed25519_ix(
+    ix: &Instruction,
+    pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<()> {
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        ErrorCode::InvalidSignature
+    );
+
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidSignature);
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let pubkey_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // u16::MAX instruction indexes point at the ed25519 instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        ErrorCode::InvalidSignature
+    );
+
+    let signed_pubkey = data.get(pubkey_offset..pubkey_offset + 32);
+    let signed_signature = data.get(signature_offset..signature_offset + 64);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    require!(
+        signed_pubkey == Some(pubkey.as_ref())
+            && signed_signature == Some(&signature[..])
+            && signed_message == Some(message),
+        ErrorCode::InvalidSignature
+    );
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ProofTag {
+    #[max_len(MAX_TAG_LEN)]
+    pub key: String,
+    #[max_len(MAX_TAG_LEN)]
+    pub value: String,
+}
+
+impl ProofTag {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.key.len() <= MAX_TAG_LEN && self.value.len() <= MAX_TAG_LEN,
+            ErrorCode::TagLimitExceeded
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ReportKind {
+    Summary,
+    Detailed,
+    Appendix,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ReportEntry {
+    pub kind: ReportKind,
+    #[max_len(MAX_CID_LEN)]
+    pub cid: String,
+}
+
+impl ReportEntry {
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.cid.is_empty(), ErrorCode::InvalidIpfsCid);
+        validate_cid_len(&self.cid)
+    }
+}
+
+// Number of findings per vulnerability class reported for a proof
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct FindingCategories {
+    pub reentrancy: u8,
+    pub integer_overflow: u8,
+    pub access_control: u8,
+    pub unchecked_input: u8,
+    pub panic: u8,
+    pub unsafe_code: u8,
+    pub race_condition: u8,
+    pub resource_exhaustion: u8,
+}
+
+impl FindingCategories {
+    pub fn count(&self, category: FindingCategory) -> u8 {
+        match category {
+            FindingCategory::Reentrancy => self.reentrancy,
+            FindingCategory::IntegerOverflow => self.integer_overflow,
+            FindingCategory::AccessControl => self.access_control,
+            FindingCategory::UncheckedInput => self.unchecked_input,
+            FindingCategory::Panic => self.panic,
+            FindingCategory::UnsafeCode => self.unsafe_code,
+            FindingCategory::RaceCondition => self.race_condition,
+            FindingCategory::ResourceExhaustion => self.resource_exhaustion,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FindingCategory {
+    Reentrancy,
+    IntegerOverflow,
+    AccessControl,
+    UncheckedInput,
+    Panic,
+    UnsafeCode,
+    RaceCondition,
+    ResourceExhaustion,
+}
+
+impl TryFrom<u8> for FindingCategory {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(FindingCategory::Reentrancy),
+            1 => Ok(FindingCategory::IntegerOverflow),
+            2 => Ok(FindingCategory::AccessControl),
+            3 => Ok(FindingCategory::UncheckedInput),
+            4 => Ok(FindingCategory::Panic),
+            5 => Ok(FindingCategory::UnsafeCode),
+            6 => Ok(FindingCategory::RaceCondition),
+            7 => Ok(FindingCategory::ResourceExhaustion),
+            _ => err!(ErrorCode::InvalidCategory),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha256,
+    Keccak256,
+    Blake3,
+}
+
+impl TryFrom<u8> for HashAlgo {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(HashAlgo::Sha256),
+            1 => Ok(HashAlgo::Keccak256),
+            2 => Ok(HashAlgo::Blake3),
+            _ => err!(ErrorCode::UnsupportedHashAlgo),
+        }
+    }
+}
+
+#[event]
+pub struct ProofSubmitted {
+    pub contract_hash: [u8; 32],
+    pub report_hash: [u8; 32],
+    pub contract_address: Pubkey,
+    pub audit_score: u8,
+    pub risk_score: u64,
+    pub timestamp: i64,
+    pub oracle: Pubkey,
+    pub nonce: u64, // per-oracle, strictly increasing
+    pub tool_version: u32,
+}
+
+#[event]
+pub struct SubmissionFeePaid {
+    pub oracle: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProofSuperseded {
+    pub old_hash: [u8; 32],
+    pub new_hash: [u8; 32],
+}
+
+#[event]
+pub struct VerificationUpdated {
+    pub contract_hash: [u8; 32],
+    pub verified: bool,
+    pub signatures: u8,
+    pub required_signatures: u8,
+}
+
+#[event]
+pub struct VerificationChanged {
+    pub contract_hash: [u8; 32],
+    pub verified: bool,
+    pub reason_cid: String,
+}
+
+#[event]
+pub struct ProofRetrieved {
+    pub contract_hash: [u8; 32],
+    pub report_hash: [u8; 32],
+    pub hash_algo: u8,
+    pub contract_address: Pubkey,
+    pub ipfs_cid: String,
+    pub audit_score: u8,
+    pub risk_score: u64,
+    pub timestamp: i64,
+    pub verified: bool,
+    pub stale: bool,
+    pub tags: Vec<ProofTag>,
+    pub findings: FindingCategories,
+    pub report_cids: Vec<ReportEntry>,
+    pub tool_version: u32,
+    pub audit_method: u8,
+}
+
+#[event]
+pub struct ProofMigrated {
+    pub contract_hash: [u8; 32],
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct ProofExpiredSwept {
+    pub contract_hash: [u8; 32],
+    pub keeper: Pubkey,
+}
+
+#[event]
+pub struct MutabilityStatus {
+    pub contract_address: Pubkey,
+    pub mutable: bool,
+    pub upgrade_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct SignatureProgress {
+    pub contract_hash: [u8; 32],
+    pub current: u8,
+    pub required: u8,
+}
+
+#[event]
+pub struct CloseFeeCollected {
+    pub amount: u64, // lamports sent to the treasury
+}
+
+#[event]
+pub struct ProofRevoked {
+    pub contract_hash: [u8; 32],
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct HistoryAppended {
+    pub contract_address: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub verified: bool,
+    pub entries: u8,
+}
+
+#[event]
+pub struct AuditHistoryRetrieved {
+    pub contract_address: Pubkey,
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[event]
+pub struct AddressResolved {
+    pub contract_address: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub oracle: Pubkey,
+}
+
+#[event]
+pub struct ReportAdded {
+    pub contract_hash: [u8; 32],
+    pub kind: ReportKind,
+    pub count: u8,
+}
+
+#[event]
+pub struct ProofSealed {
+    pub contract_hash: [u8; 32],
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct ProofFieldUpdated {
+    pub contract_hash: [u8; 32],
+    pub field: String,
+    pub version: u32,
+}
+
+#[event]
+pub struct ProofComparison {
+    pub contract_address: Pubkey,
+    pub old_score: u8,
+    pub new_score: u8,
+    pub old_risk: u64,
+    pub new_risk: u64,
+    pub improved: bool,
+}
+
+#[event]
+pub struct SubmissionValidation {
+    pub ok: bool,
+    pub failing_field: Option<String>,
+}
+
+#[event]
+pub struct ProofLookup {
+    pub contract_hash: [u8; 32],
+    pub oracle: Pubkey,
+    pub exists: bool,
+}
+
+#[event]
+pub struct ProofExists {
+    pub contract_hash: [u8; 32],
+    pub exists: bool,
+}
+
+#[event]
+pub struct VerifiedCheck {
+    pub contract_address: Pubkey,
+    pub ok: bool,
+    pub stale: bool, // verified longer ago than the configured TTL
+}
+
+#[event]
+pub struct EffectiveScore {
+    pub contract_hash: [u8; 32],
+    pub score: u8,
+}
+
+#[event]
+pub struct ContentVerified {
+    pub contract_hash: [u8; 32],
+    pub matched: bool,
+}
+
+#[event]
+pub struct AuditVerified {
+    pub contract_hash: [u8; 32],
+    pub contract_address: Pubkey,
+    pub audit_score: u8,
+    pub verification_timestamp: i64,
+}
+
+#[event]
+pub struct BatchVerified {
+    pub oracle: Pubkey,
+    pub count: u32,
+}
+
+#[event]
+pub struct RiskHistogram {
+    pub counts: Vec<u32>,
+}
+
+#[event]
+pub struct PortfolioRisk {
+    pub average: u64,
+    pub count: u32,
+}
+
+#[event]
+pub struct SummaryBatch {
+    pub count: u32,
+}
+
+#[event]
+pub struct OracleAdded {
+    pub oracle: Pubkey,
+    pub role: u8,
+}
+
+#[event]
+pub struct OracleEvicted {
+    pub evicted: Pubkey,
+    pub added: Pubkey,
+}
+
+#[event]
+pub struct ActionQueued {
+    pub action_id: u64,
+    pub action_type: u8,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ActionExecuted {
+    pub action_id: u64,
+    pub action_type: u8,
+}
+
+#[event]
+pub struct ActionCancelled {
+    pub action_id: u64,
+    pub action_type: u8,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub update: ConfigUpdate,
+}
+
+#[event]
+pub struct ConfigSnapshot {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub fee_split_bps: u16,
+    pub submission_fee: u64,
+    pub min_oracle_stake: u64,
+    pub daily_reward_cap: u64,
+    pub rewards_frozen: bool,
+    pub staking_pool: Pubkey,
+    pub treasury: Pubkey,
+    pub round_up_rewards: bool,
+    pub audit_fee: u64,
+    pub keeper_fee: u64,
+    pub claim_cooldown: u64,
+    pub activity_bonus_bps: u16,
+    pub verification_ttl: u64,
+    pub oracle_fee_bps: u16,
+    pub max_submissions_per_day: u16,
+    pub governance_bonus_bps: u16,
+    pub max_bonus_votes: u64,
+    pub slash_distribution: SlashDistribution,
+    pub decay_bps_per_day: u16,
+    pub min_proposal_stake: u64,
+    pub reward_pool: Pubkey,
+    pub oracle_reward_source: RewardSource,
+    pub delegation_cooldown: u64,
+    pub max_total_reward_multiple: u16,
+    pub pinning_pubkey: Pubkey,
+    pub risk_bands: RiskBands,
+    pub badge_mint: Pubkey,
+    pub badge_bonus_bps: u16,
+    pub timelock_delay: u64,
+    pub withdrawal_delay: u64,
+    pub consensus_window: u64,
+    pub expiry_sweep_fee: u64,
+    pub max_vote_weight_bps: u16,
+    pub accepted_fee_mints: Vec<Pubkey>,
+    pub min_tool_version: u32,
+    pub close_fee_bps: u16,
+    pub manual_review_risk_threshold: u64,
+    pub min_restake_principal: u64,
+    pub max_oracle_reward: u64,
+}
+
+#[event]
+pub struct RewardsFrozen {
+    pub frozen: bool,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    #[msg("Invalid risk score")]
+    InvalidRiskScore,
+    #[msg("Proof not found")]
+    ProofNotFound,
+    #[msg("Hash mismatch")]
+    HashMismatch,
+    #[msg("Invalid audit score")]
+    InvalidAuditScore,
+    #[msg("Account count mismatch")]
+    AccountCountMismatch,
+    #[msg("Unsupported hash algorithm")]
+    UnsupportedHashAlgo,
+    #[msg("Proof already superseded")]
+    AlreadySuperseded,
+    #[msg("Tag limit exceeded")]
+    TagLimitExceeded,
+    #[msg("Submission fee or minimum oracle stake required")]
+    SubmissionRequirementNotMet,
+    #[msg("Return data too large")]
+    ReturnDataTooLarge,
+    #[msg("Proof already migrated")]
+    AlreadyMigrated,
+    #[msg("Unsupported account layout")]
+    UnsupportedLayout,
+    #[msg("Invalid IPFS CID")]
+    InvalidIpfsCid,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Invalid signature")]
+    InvalidSignature,
+    #[msg("Proof has been revoked")]
+    ProofRevoked,
+    #[msg("Oracle daily submission limit reached")]
+    RateLimitExceeded,
+    #[msg("Unknown finding category")]
+    InvalidCategory,
+    #[msg("Oracle stake below the required minimum")]
+    InsufficientOracleStake,
+    #[msg("Slash distribution must sum to 10000 bps")]
+    InvalidSlashDistribution,
+    #[msg("Unexpected submission nonce")]
+    InvalidNonce,
+    #[msg("Proof is not verified")]
+    ProofNotVerified,
+    #[msg("Proof verification has expired")]
+    ProofExpired,
+    #[msg("Proofs are for different contracts")]
+    ContractMismatch,
+    #[msg("Oracle already signed this proof")]
+    AlreadySigned,
+    #[msg("Invalid risk band configuration")]
+    InvalidRiskBands,
+    #[msg("Proof already has the maximum number of verifier signatures")]
+    TooManyVerifiers,
+    #[msg("Admin changes must be queued while a timelock is set")]
+    TimelockRequired,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Unknown admin action type")]
+    InvalidActionType,
+    #[msg("Admin action parameters could not be decoded")]
+    InvalidActionParams,
+    #[msg("Histogram buckets must be strictly ascending")]
+    InvalidBuckets,
+    #[msg("Oracle registry is full")]
+    RegistryFull,
+    #[msg("Oracle is already registered")]
+    OracleAlreadyRegistered,
+    #[msg("Invalid oracle registry size")]
+    InvalidRegistrySize,
+    #[msg("Proof is sealed")]
+    ProofSealed,
+    #[msg("Too many report CIDs")]
+    TooManyReports,
+    #[msg("Verification consensus window expired")]
+    ConsensusExpired,
+    #[msg("Proof has not expired yet")]
+    ProofNotExpired,
+    #[msg("Proof was already swept as expired")]
+    AlreadySwept,
+    #[msg("Too many accepted fee mints")]
+    TooManyFeeMints,
+    #[msg("Analyzer version is older than the supported minimum")]
+    ToolVersionTooOld,
+    #[msg("Unknown oracle role")]
+    InvalidOracleRole,
+    #[msg("Proof is still active")]
+    ProofStillActive,
+    #[msg("Unknown audit method")]
+    InvalidAuditMethod,
+    #[msg("Risk level requires a manual or hybrid review")]
+    ManualReviewRequired,
+    #[msg("Clock sysvar unavailable")]
+    ClockUnavailable,
+    #[msg("Too many allowed verifiers")]
+    TooManyAllowedVerifiers,
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBps,
+    #[msg("Account is not rent exempt")]
+    NotRentExempt,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Invalid duration")]
+    InvalidDuration,
+    #[msg("Stake not unlocked")]
+    StakeNotUnlocked,
+    #[msg("No rewards available")]
+    NoRewardsAvailable,
+    #[msg("Insufficient stake")]
+    InsufficientStake,
+    #[msg("Invalid vote weight")]
+    InvalidVoteWeight,
+    #[msg("Stake is locked for voting")]
+    StakeLockedForVoting,
+    #[msg("Voting has closed")]
+    VotingClosed,
+    #[msg("Voting has not ended")]
+    VotingNotEnded,
+    #[msg("Proposal already finalized")]
+    ProposalAlreadyFinalized,
+    #[msg("Invalid pass threshold")]
+    InvalidThreshold,
+    #[msg("Too many stakes")]
+    TooManyStakes,
+    #[msg("Rewards are frozen")]
+    RewardsFrozen,
+    #[msg("Stake created after proposal snapshot")]
+    StakeTooRecent,
+    #[msg("Insufficient reward reserve")]
+    InsufficientRewardReserve,
+    #[msg("Grace period has not elapsed")]
+    GracePeriodNotElapsed,
+    #[msg("Claim cooldown active")]
+    ClaimCooldownActive,
+    #[msg("Escrow already released")]
+    EscrowAlreadyReleased,
+    #[msg("Escrow cannot be released yet")]
+    EscrowNotReleasable,
+    #[msg("Reward cliff not reached")]
+    CliffNotReached,
+    #[msg("Too many treasury signers")]
+    TooManySigners,
+    #[msg("Duplicate treasury signer")]
+    DuplicateSigner,
+    #[msg("Signer already approved")]
+    AlreadyApproved,
+    #[msg("Withdrawal already executed")]
+    WithdrawalAlreadyExecuted,
+    #[msg("Stake is bonded to an open proposal")]
+    ProposalBondActive,
+    #[msg("Account required by the reward source is missing")]
+    RewardSourceAccountMissing,
+    #[msg("Delegation changed too recently")]
+    DelegationCooldown,
+    #[msg("Stake is not delegated")]
+    NotDelegated,
+    #[msg("Unknown vote type")]
+    InvalidVoteType,
+    #[msg("Claimable rewards are below the requested minimum")]
+    RewardBelowMinimum,
+    #[msg("Audit score meets the SLA")]
+    SlaNotBreached,
+    #[msg("SLA refund already paid")]
+    SlaAlreadyRefunded,
+    #[msg("Stake is under a compliance hold")]
+    StakeHeld,
+    #[msg("Stake principal is below the auto-restake minimum")]
+    RestakeBelowMinimum,
+    #[msg("Oracle was already rewarded for this proof")]
+    OracleAlreadyRewarded,
+    #[msg("Reward exceeds the configured treasury cap")]
+    RewardAboveCap,
+    #[msg("Escrow was funded without an SLA")]
+    NoSlaAgreed,
+    #[msg("Token account mint does not match")]
+    MintMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_time_fails_closed_without_clock() {
+        // Off-chain there is no Clock sysvar to read
+        assert_eq!(current_time().unwrap_err(), error!(ErrorCode::ClockUnavailable));
+    }
+
+    fn verified_proof() -> AuditProof {
+        AuditProof {
+            verified: true,
+            expiry_timestamp: 1_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn assert_verified_proof_accepts_live_proof() {
+        assert!(assert_verified_proof(&verified_proof(), 1_000).is_ok());
+    }
+
+    #[test]
+    fn assert_verified_proof_rejects_unverified() {
+        let proof = AuditProof {
+            verified: false,
+            ..verified_proof()
+        };
+        assert_eq!(
+            assert_verified_proof(&proof, 500).unwrap_err(),
+            error!(ErrorCode::ProofNotVerified)
+        );
+    }
+
+    #[test]
+    fn assert_verified_proof_rejects_revoked() {
+        let proof = AuditProof {
+            revoked: true,
+            ..verified_proof()
+        };
+        assert_eq!(assert_verified_proof(&proof, 500).unwrap_err(), error!(ErrorCode::ProofRevoked));
+    }
+
+    #[test]
+    fn assert_verified_proof_rejects_expired() {
+        assert_eq!(
+            assert_verified_proof(&verified_proof(), 1_001).unwrap_err(),
+            error!(ErrorCode::ProofExpired)
+        );
+
+        // Swept proofs stay expired whatever the clock says
+        let swept = AuditProof {
+            expired: true,
+            ..verified_proof()
+        };
+        assert_eq!(assert_verified_proof(&swept, 500).unwrap_err(), error!(ErrorCode::ProofExpired));
+    }
 }
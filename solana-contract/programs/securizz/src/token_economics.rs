@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, Transfer};
+use anchor_lang::solana_program::log::sol_log_compute_units;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{self, Burn, SetAuthority, Token, TokenAccount, Mint, MintTo, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
 
-declare_id!("ReplaceWithDeployedProgramId");
+use crate::utils::{apply_bps, assert_rent_exempt, validate_bps, SECONDS_PER_DAY};
+use crate::{current_time, AuditProof, ErrorCode, ProgramConfig, RewardSource};
 
-#[program]
 pub mod securizz_tokenomics {
     use super::*;
 
@@ -13,12 +16,37 @@ pub mod securizz_tokenomics {
         ctx: Context<InitializeToken>,
         decimals: u8,
     ) -> Result<()> {
-        let mint = &mut ctx.accounts.mint;
-        mint.mint_authority = COption::Some(ctx.accounts.authority.key());
-        mint.supply = 0;
-        mint.decimals = decimals;
-        mint.is_initialized = true;
-        mint.freeze_authority = COption::Some(ctx.accounts.authority.key());
+        let mint = &ctx.accounts.mint;
+        let now = current_time()?;
+        
+        // Mint fields are set by the init constraint; confirm the signer holds both authorities
+        require!(
+            mint.mint_authority == COption::Some(ctx.accounts.authority.key())
+                && mint.freeze_authority == COption::Some(ctx.accounts.authority.key()),
+            ErrorCode::Unauthorized
+        );
+        
+        // Record the initializer; the meta PDA can only be created once per mint
+        let token_meta = &mut ctx.accounts.token_meta;
+        token_meta.mint = mint.key();
+        token_meta.authority = ctx.accounts.authority.key();
+        token_meta.decimals = decimals;
+        token_meta.initialized_at = now;
+        
+        emit!(TokenInitialized {
+            mint: token_meta.mint,
+            authority: token_meta.authority,
+            decimals,
+        });
+        
+        Ok(())
+    }
+
+    // Create the global staking statistics account
+    pub fn initialize_staking_stats(ctx: Context<InitializeStakingStats>) -> Result<()> {
+        ctx.accounts.staking_stats.audit_count = 0;
+        ctx.accounts.staking_stats.total_staked = 0;
+        ctx.accounts.staking_stats.solvency_ratio = 10_000;
         Ok(())
     }
 
@@ -27,19 +55,49 @@ pub mod securizz_tokenomics {
         ctx: Context<StakeTokens>,
         amount: u64,
         duration: u64, // in seconds
+        grace_period: u64, // seconds after unlock before anyone may auto-restake
+        cliff_seconds: u64, // no rewards accrue until this long after staking
     ) -> Result<()> {
         let stake_account = &mut ctx.accounts.stake_account;
         let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let slot = clock.slot;
         
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(duration >= 86400, ErrorCode::InvalidDuration); // Minimum 1 day
+        require!(duration >= SECONDS_PER_DAY, ErrorCode::InvalidDuration); // Minimum 1 day
         
         stake_account.user = ctx.accounts.user.key();
         stake_account.amount = amount;
         stake_account.duration = duration;
-        stake_account.staked_at = clock.unix_timestamp;
-        stake_account.unlock_time = clock.unix_timestamp + duration as i64;
+        stake_account.staked_at = now;
+        stake_account.staked_slot = slot;
+        stake_account.unlock_time = now + duration as i64;
         stake_account.rewards_claimed = 0;
+        stake_account.locked_until = 0;
+        stake_account.grace_period = grace_period;
+        stake_account.cliff_seconds = cliff_seconds;
+        stake_account.last_claim_time = 0;
+        stake_account.audit_count_snapshot = ctx.accounts.staking_stats.audit_count;
+        stake_account.fees_per_share_snapshot = ctx.accounts.staking_stats.fees_per_share;
+        stake_account.votes_cast = 0;
+        stake_account.position_mint = ctx.accounts.position_mint.key();
+        stake_account.bonded_proposal = None;
+        stake_account.delegate = None;
+        stake_account.delegation_changed_at = 0;
+        stake_account.bump = ctx.bumps.stake_account;
+        stake_account.accrued_rewards = 0;
+        stake_account.reward_rate_bps = lock_tier_rate_bps(duration);
+        stake_account.held = false;
+        
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        staking_stats.total_staked = staking_stats.total_staked.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        
+        let stake_index = &mut ctx.accounts.stake_index;
+        stake_index.user = ctx.accounts.user.key();
+        if !stake_index.stakes.contains(&stake_account.key()) {
+            require!(stake_index.stakes.len() < MAX_USER_STAKES, ErrorCode::TooManyStakes);
+            stake_index.stakes.push(stake_account.key());
+        }
         
         // Transfer tokens to staking pool
         let cpi_accounts = Transfer {
@@ -51,6 +109,27 @@ pub mod securizz_tokenomics {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, amount)?;
         
+        // Mint the position receipt, then drop the mint authority so it stays a single token
+        let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.position_mint.to_account_info(),
+            to: ctx.accounts.position_token_account.to_account_info(),
+            authority: ctx.accounts.staking_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::mint_to(cpi_ctx, 1)?;
+        
+        let cpi_accounts = SetAuthority {
+            account_or_mint: ctx.accounts.position_mint.to_account_info(),
+            current_authority: ctx.accounts.staking_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+        
+        assert_rent_exempt(&stake_account.to_account_info())?;
+        
         emit!(TokensStaked {
             user: ctx.accounts.user.key(),
             amount,
@@ -58,211 +137,2240 @@ pub mod securizz_tokenomics {
             unlock_time: stake_account.unlock_time,
         });
         
+        emit!(PositionMinted {
+            stake_account: stake_account.key(),
+            position_mint: stake_account.position_mint,
+            owner: ctx.accounts.user.key(),
+        });
+        
         Ok(())
     }
 
     // Claim staking rewards
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+        min_amount: Option<u64>, // fail rather than accept less than this
+    ) -> Result<()> {
         let stake_account = &mut ctx.accounts.stake_account;
-        let clock = Clock::get()?;
+        let now = current_time()?;
+        
+        require!(!ctx.accounts.config.rewards_frozen, ErrorCode::RewardsFrozen);
+        require!(!stake_account.held, ErrorCode::StakeHeld);
+        require!(now >= stake_account.unlock_time, ErrorCode::StakeNotUnlocked);
+        require!(now >= stake_account.accrual_start(), ErrorCode::CliffNotReached);
+        
+        let next_time = stake_account.last_claim_time + ctx.accounts.config.claim_cooldown as i64;
+        if stake_account.last_claim_time > 0 && now < next_time {
+            // Logged for simulation callers; the failed transaction discards it on-chain
+            emit!(ClaimTooSoon { next_time });
+            return err!(ErrorCode::ClaimCooldownActive);
+        }
         
-        require!(clock.unix_timestamp >= stake_account.unlock_time, ErrorCode::StakeNotUnlocked);
+        // Bracketed by compute unit logs so tests can hold the reward math to its budget
+        sol_log_compute_units();
+        let rewards = stake_account.settle_rewards(now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+        sol_log_compute_units();
         
-        // Calculate rewards (1% daily)
-        let staking_duration = clock.unix_timestamp - stake_account.staked_at;
-        let daily_rewards = stake_account.amount / 100; // 1% daily
-        let total_rewards = (daily_rewards * staking_duration as u64) / 86400;
-        let claimable_rewards = total_rewards - stake_account.rewards_claimed;
+        // Extrapolate this claim across all staked principal to estimate what the pool
+        // owes; when the reserve can't cover that, every claim is paid the same fraction
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        let total_pending = if stake_account.amount > 0 {
+            rewards.claimable as u128 * staking_stats.total_staked.max(stake_account.amount) as u128
+                / stake_account.amount as u128
+        } else {
+            rewards.claimable as u128
+        };
+        let reserve = ctx.accounts.reward_pool.amount as u128;
+        staking_stats.solvency_ratio = if total_pending > reserve {
+            (reserve * 10_000 / total_pending) as u16
+        } else {
+            10_000
+        };
+        let claimable_rewards = if staking_stats.solvency_ratio < 10_000 {
+            emit!(RewardRateReduced { factor: staking_stats.solvency_ratio });
+            apply_bps(rewards.claimable, staking_stats.solvency_ratio)
+        } else {
+            rewards.claimable
+        };
         
         require!(claimable_rewards > 0, ErrorCode::NoRewardsAvailable);
+        if let Some(min_amount) = min_amount {
+            require!(claimable_rewards >= min_amount, ErrorCode::RewardBelowMinimum);
+        }
+        
+        // The badge bonus is paid on top and does not count against accrued rewards
+        let holds_badge = ctx.accounts.auditor_badge.as_ref().map_or(false, |badge| badge.amount >= 1);
+        let badge_bonus = if holds_badge {
+            apply_bps(claimable_rewards, ctx.accounts.config.badge_bonus_bps)
+        } else {
+            0
+        };
+        let payout = claimable_rewards.checked_add(badge_bonus).ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            payout <= ctx.accounts.reward_pool.amount,
+            ErrorCode::InsufficientRewardReserve
+        );
         
-        stake_account.rewards_claimed += claimable_rewards;
+        stake_account.accrued_rewards = 0;
+        stake_account.last_claim_time = now;
         
-        // Transfer rewards to user
+        // Transfer rewards to user, or to the destination they chose
+        let to = match &ctx.accounts.destination {
+            Some(destination) => destination.to_account_info(),
+            None => ctx.accounts.user_token_account.to_account_info(),
+        };
         let cpi_accounts = Transfer {
-            from: ctx.accounts.staking_pool.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
+            from: ctx.accounts.reward_pool.to_account_info(),
+            to,
             authority: ctx.accounts.staking_authority.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&ctx.accounts.staking_authority_seeds]);
-        token::transfer(cpi_ctx, claimable_rewards)?;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, payout)?;
         
         emit!(RewardsClaimed {
             user: ctx.accounts.user.key(),
-            amount: claimable_rewards,
+            amount: payout,
             total_claimed: stake_account.rewards_claimed,
+            capped: rewards.capped,
+            time_rewards: rewards.time_rewards,
+            activity_rewards: rewards.activity_rewards,
+            governance_rewards: rewards.governance_rewards,
+            badge_bonus,
         });
         
         Ok(())
     }
 
-    // Pay for audit with SECURIZZ tokens
-    pub fn pay_for_audit(
-        ctx: Context<PayForAudit>,
-        audit_fee: u64,
-    ) -> Result<()> {
-        require!(audit_fee > 0, ErrorCode::InvalidAmount);
+    // Preview claimable rewards without transferring
+    pub fn claimable_preview(ctx: Context<PreviewClaim>) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        let now = current_time()?;
         
-        // Transfer payment to treasury
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.treasury.to_account_info(),
+        let (claimable, next_claim_time) = if now < stake_account.unlock_time {
+            (0, stake_account.unlock_time)
+        } else {
+            let rewards = stake_account.claimable_rewards(now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+            (rewards.claimable, now)
+        };
+        
+        emit!(ClaimablePreview {
+            user: stake_account.user,
+            claimable,
+            next_claim_time,
+        });
+        
+        Ok(())
+    }
+
+    // Sum of claimable rewards across the signer's stakes, passed as remaining accounts
+    pub fn total_claimable<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReadStakes<'info>>,
+    ) -> Result<u64> {
+        let user = ctx.accounts.user.key();
+        let now = current_time()?;
+        
+        let mut amount: u64 = 0;
+        for account_info in ctx.remaining_accounts.iter() {
+            let stake_account = Account::<StakeAccount>::try_from(account_info)?;
+            require!(stake_account.user == user, ErrorCode::Unauthorized);
+            
+            if now < stake_account.unlock_time {
+                continue;
+            }
+            
+            let rewards = stake_account.claimable_rewards(now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+            amount = amount.checked_add(rewards.claimable).ok_or(ErrorCode::MathOverflow)?;
+        }
+        
+        emit!(TotalClaimable { user, amount });
+        
+        Ok(amount)
+    }
+
+    // Withdraw staked principal once the lock has expired
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        let now = current_time()?;
+        
+        require!(now >= stake_account.unlock_time, ErrorCode::StakeNotUnlocked);
+        require!(now >= stake_account.locked_until, ErrorCode::StakeLockedForVoting);
+        require!(stake_account.bonded_proposal.is_none(), ErrorCode::ProposalBondActive);
+        
+        let amount = stake_account.amount;
+        
+        let stake_key = stake_account.key();
+        ctx.accounts.stake_index.stakes.retain(|stake| *stake != stake_key);
+        
+        // Stakes opened before total_staked was tracked were never counted
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        staking_stats.total_staked = staking_stats.total_staked.saturating_sub(amount);
+        
+        // Whoever holds the position receipt owns the principal; the receipt is consumed
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.position_mint.to_account_info(),
+            from: ctx.accounts.position_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, audit_fee)?;
+        token::burn(cpi_ctx, 1)?;
         
-        emit!(AuditPaid {
+        emit!(PositionBurned {
+            stake_account: stake_key,
+            position_mint: stake_account.position_mint,
+            owner: ctx.accounts.user.key(),
+        });
+        
+        // Return principal to the position holder
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.staking_pool.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.staking_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+        
+        emit!(StakeWithdrawn {
             user: ctx.accounts.user.key(),
-            amount: audit_fee,
-            contract_hash: ctx.accounts.audit_proof.contract_hash,
+            amount,
         });
         
         Ok(())
     }
 
-    // Governance voting with staked tokens
-    pub fn vote_on_proposal(
-        ctx: Context<VoteOnProposal>,
-        proposal_id: u64,
-        vote_weight: u64,
-        support: bool,
-    ) -> Result<()> {
-        let vote_account = &mut ctx.accounts.vote_account;
-        let stake_account = &ctx.accounts.stake_account;
+    // Take principal out of the stake now and queue it for payout after withdrawal_delay
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = current_time()?;
         
-        require!(stake_account.amount >= vote_weight, ErrorCode::InsufficientStake);
-        require!(vote_weight > 0, ErrorCode::InvalidVoteWeight);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= stake_account.amount, ErrorCode::InsufficientStake);
+        require!(now >= stake_account.unlock_time, ErrorCode::StakeNotUnlocked);
+        require!(now >= stake_account.locked_until, ErrorCode::StakeLockedForVoting);
+        require!(stake_account.bonded_proposal.is_none(), ErrorCode::ProposalBondActive);
         
-        vote_account.proposal_id = proposal_id;
-        vote_account.voter = ctx.accounts.voter.key();
-        vote_account.vote_weight = vote_weight;
-        vote_account.support = support;
-        vote_account.voted_at = Clock::get()?.unix_timestamp;
+        stake_account.reduce_principal(amount, now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        staking_stats.total_staked = staking_stats.total_staked.saturating_sub(amount);
         
-        emit!(VoteCast {
-            proposal_id,
-            voter: ctx.accounts.voter.key(),
-            vote_weight,
-            support,
+        let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+        withdrawal_request.stake_account = stake_account.key();
+        withdrawal_request.user = ctx.accounts.user.key();
+        withdrawal_request.amount = amount;
+        withdrawal_request.ready_at = now
+            .checked_add(ctx.accounts.config.withdrawal_delay as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        withdrawal_request.bump = ctx.bumps.withdrawal_request;
+        
+        emit!(WithdrawalRequested {
+            stake_account: stake_account.key(),
+            user: ctx.accounts.user.key(),
+            amount,
+            ready_at: withdrawal_request.ready_at,
         });
         
         Ok(())
     }
-}
-
-#[derive(Accounts)]
-pub struct InitializeToken<'info> {
-    #[account(
-        init,
-        payer = authority,
-        mint::decimals = 9,
-        mint::authority = authority,
-    )]
-    pub mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
 
-#[derive(Accounts)]
-pub struct StakeTokens<'info> {
-    #[account(
-        init,
-        payer = user,
-        space = 8 + StakeAccount::INIT_SPACE,
-        seeds = [b"stake", user.key().as_ref()],
-        bump
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub staking_pool: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+        let withdrawal_request = &ctx.accounts.withdrawal_request;
+        let now = current_time()?;
+        
+        require!(now >= withdrawal_request.ready_at, ErrorCode::TimelockNotElapsed);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.staking_pool.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.staking_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, withdrawal_request.amount)?;
+        
+        emit!(WithdrawalClaimed {
+            stake_account: withdrawal_request.stake_account,
+            user: withdrawal_request.user,
+            amount: withdrawal_request.amount,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(
-        mut,
-        seeds = [b"stake", user.key().as_ref()],
-        bump
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub staking_pool: Account<'info, TokenAccount>,
-    /// CHECK: This is the staking authority PDA
-    #[account(
-        seeds = [b"staking_authority"],
-        bump
-    )]
-    pub staking_authority: UncheckedAccount<'info>,
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    // Hand a stake position to another user without unstaking
+    pub fn transfer_stake(
+        ctx: Context<TransferStake>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let now = current_time()?;
+        let stake_account = &mut ctx.accounts.stake_account;
+        
+        require!(now >= stake_account.locked_until, ErrorCode::StakeLockedForVoting);
+        require!(stake_account.bonded_proposal.is_none(), ErrorCode::ProposalBondActive);
+        
+        let stake_key = stake_account.key();
+        ctx.accounts.from_index.stakes.retain(|stake| *stake != stake_key);
+        
+        let to_index = &mut ctx.accounts.to_index;
+        to_index.user = new_owner;
+        if !to_index.stakes.contains(&stake_key) {
+            require!(to_index.stakes.len() < MAX_USER_STAKES, ErrorCode::TooManyStakes);
+            to_index.stakes.push(stake_key);
+        }
+        
+        // Lock, accrual and claim history travel with the position; the delegation does not
+        let from = stake_account.user;
+        stake_account.user = new_owner;
+        stake_account.delegate = None;
+        
+        // The position receipt is what withdraw_stake checks, so it moves with the stake
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.position_token_account.to_account_info(),
+            to: ctx.accounts.new_owner_position_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, 1)?;
+        
+        emit!(StakeTransferred {
+            from,
+            to: new_owner,
+            amount: stake_account.amount,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct PayForAudit<'info> {
-    #[account(mut)]
-    pub audit_proof: Account<'info, AuditProof>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub treasury: Account<'info, TokenAccount>,
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    // Let another key vote with this stake
+    pub fn delegate_votes(ctx: Context<ChangeDelegation>, delegate: Pubkey) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = current_time()?;
+        
+        stake_account.check_delegation_cooldown(now, &ctx.accounts.config)?;
+        
+        stake_account.delegate = Some(delegate);
+        stake_account.delegation_changed_at = now;
+        
+        emit!(VotesDelegated {
+            stake_account: stake_account.key(),
+            owner: stake_account.user,
+            delegate,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct VoteOnProposal<'info> {
-    #[account(
-        init,
-        payer = voter,
-        space = 8 + VoteAccount::INIT_SPACE,
-        seeds = [b"vote", proposal_id.to_le_bytes().as_ref(), voter.key().as_ref()],
-        bump
-    )]
-    pub vote_account: Account<'info, VoteAccount>,
-    #[account(
-        seeds = [b"stake", voter.key().as_ref()],
-        bump
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut)]
-    pub voter: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    // Return voting power to the stake owner
+    pub fn revoke_delegation(ctx: Context<ChangeDelegation>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = current_time()?;
+        
+        let delegate = stake_account.delegate.take().ok_or(ErrorCode::NotDelegated)?;
+        stake_account.delegation_changed_at = now;
+        
+        emit!(DelegationRevoked {
+            stake_account: stake_account.key(),
+            owner: stake_account.user,
+            delegate,
+        });
+        
+        Ok(())
+    }
 
-#[account]
-pub struct StakeAccount {
-    pub user: Pubkey,
-    pub amount: u64,
-    pub duration: u64,
-    pub staked_at: i64,
-    pub unlock_time: i64,
-    pub rewards_claimed: u64,
-}
+    // Lengthen the lock to `new_duration` seconds from now, moving to that duration's tier
+    pub fn extend_lock(ctx: Context<ExtendLock>, new_duration: u64) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = current_time()?;
+        
+        let remaining = stake_account.unlock_time.saturating_sub(now).max(0) as u64;
+        require!(new_duration > remaining, ErrorCode::InvalidDuration);
+        require!(new_duration >= SECONDS_PER_DAY, ErrorCode::InvalidDuration);
+        
+        // Time already staked is paid at the old rate before the new tier applies
+        let new_rate = lock_tier_rate_bps(new_duration);
+        stake_account.change_rate(new_rate, now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+        stake_account.duration = new_duration;
+        stake_account.unlock_time = now
+            .checked_add(new_duration as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit!(LockExtended {
+            user: stake_account.user,
+            new_unlock_time: stake_account.unlock_time,
+            new_rate,
+        });
+        
+        Ok(())
+    }
 
-#[account]
-pub struct VoteAccount {
-    pub proposal_id: u64,
-    pub voter: Pubkey,
-    pub vote_weight: u64,
-    pub support: bool,
-    pub voted_at: i64,
-}
+    // Re-lock a stake left unwithdrawn past its grace period, paying the caller a keeper fee
+    pub fn auto_restake(ctx: Context<AutoRestake>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = current_time()?;
+        
+        let restake_time = stake_account.unlock_time + stake_account.grace_period as i64;
+        require!(now > restake_time, ErrorCode::GracePeriodNotElapsed);
+        // Dust and emptied stakes are not worth a keeper's fee
+        require!(stake_account.amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            stake_account.amount >= ctx.accounts.config.min_restake_principal,
+            ErrorCode::RestakeBelowMinimum
+        );
+        
+        // Unclaimed rewards are settled before the new lock starts accruing, and the
+        // keeper is paid out of them rather than out of everyone else's share
+        let stats = &ctx.accounts.staking_stats;
+        stake_account.settle_rewards(now, &ctx.accounts.config, stats);
+        let fee = ctx.accounts.config.keeper_fee.min(stake_account.accrued_rewards);
+        stake_account.accrued_rewards -= fee;
+        stake_account.restart_accrual(now, stats);
+        stake_account.unlock_time = now + stake_account.duration as i64;
+        
+        if fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reward_pool.to_account_info(),
+                to: ctx.accounts.keeper_token_account.to_account_info(),
+                authority: ctx.accounts.staking_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, fee)?;
+        }
+        
+        emit!(AutoRestaked {
+            user: stake_account.user,
+            keeper: ctx.accounts.keeper.key(),
+            fee,
+        });
+        
+        Ok(())
+    }
 
-#[event]
+    // Reward the oracle behind a verified proof from the configured source
+    pub fn oracle_reward(ctx: Context<OracleReward>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(ctx.accounts.audit_proof.verified, ErrorCode::ProofNotVerified);
+        require!(!ctx.accounts.audit_proof.oracle_rewarded, ErrorCode::OracleAlreadyRewarded);
+        ctx.accounts.audit_proof.oracle_rewarded = true;
+        
+        let source = ctx.accounts.config.oracle_reward_source;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        
+        match source {
+            RewardSource::Mint => {
+                let mint = ctx.accounts.mint.as_ref().ok_or(ErrorCode::RewardSourceAccountMissing)?;
+                require!(
+                    ctx.accounts.oracle_token_account.mint == mint.key(),
+                    ErrorCode::Unauthorized
+                );
+                
+                // The admin is the mint authority set in initialize_token
+                let cpi_accounts = MintTo {
+                    mint: mint.to_account_info(),
+                    to: ctx.accounts.oracle_token_account.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                token::mint_to(cpi_ctx, amount)?;
+            }
+            RewardSource::Treasury => {
+                // Larger treasury payouts go through the multisig withdrawal flow
+                require!(amount <= ctx.accounts.config.max_oracle_reward, ErrorCode::RewardAboveCap);
+                let treasury = ctx.accounts.treasury.as_ref().ok_or(ErrorCode::RewardSourceAccountMissing)?;
+                
+                let cpi_accounts = Transfer {
+                    from: treasury.to_account_info(),
+                    to: ctx.accounts.oracle_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                };
+                let signer_seeds: &[&[&[u8]]] = &[&[b"treasury_authority", &[ctx.bumps.treasury_authority]]];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token::transfer(cpi_ctx, amount)?;
+            }
+        }
+        
+        emit!(OracleRewarded {
+            oracle: ctx.accounts.audit_proof.oracle,
+            contract_hash: ctx.accounts.audit_proof.contract_hash,
+            amount,
+            source,
+        });
+        
+        Ok(())
+    }
+
+    // Top up the reward pool; anyone may fund it
+    pub fn refill_reward_pool(ctx: Context<RefillRewardPool>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_pool.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        ctx.accounts.reward_pool.reload()?;
+        
+        emit!(RewardPoolRefilled {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            balance: ctx.accounts.reward_pool.amount,
+        });
+        
+        Ok(())
+    }
+
+    // Slash an oracle's stake and distribute it per the configured split
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= stake_account.amount, ErrorCode::InsufficientStake);
+        
+        stake_account.amount -= amount;
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        staking_stats.total_staked = staking_stats.total_staked.saturating_sub(amount);
+        
+        let (burned, to_treasury, to_rewards) = ctx.accounts.config.slash_distribution.split(amount);
+        let signer_seeds: &[&[&[u8]]] = &[&[b"staking_authority", &[ctx.bumps.staking_authority]]];
+        
+        if burned > 0 {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.staking_pool.to_account_info(),
+                authority: ctx.accounts.staking_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::burn(cpi_ctx, burned)?;
+        }
+        
+        if to_treasury > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.staking_pool.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.staking_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, to_treasury)?;
+        }
+        
+        if to_rewards > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.staking_pool.to_account_info(),
+                to: ctx.accounts.reward_pool.to_account_info(),
+                authority: ctx.accounts.staking_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, to_rewards)?;
+        }
+        
+        emit!(SlashDistributed {
+            oracle: stake_account.user,
+            burned,
+            to_treasury,
+            to_rewards,
+        });
+        
+        Ok(())
+    }
+
+    // Compliance hold: the stake keeps accruing but rewards can't be claimed until released
+    pub fn hold_stake(ctx: Context<SetStakeHold>, user: Pubkey) -> Result<()> {
+        ctx.accounts.stake_account.held = true;
+
+        emit!(StakeHeld { user });
+
+        Ok(())
+    }
+
+    pub fn release_stake(ctx: Context<SetStakeHold>, user: Pubkey) -> Result<()> {
+        ctx.accounts.stake_account.held = false;
+
+        emit!(StakeReleased { user });
+
+        Ok(())
+    }
+
+    // Hold an audit payment until the linked proof is verified or revoked
+    pub fn escrow_audit_payment(
+        ctx: Context<EscrowAuditPayment>,
+        amount: u64,
+        min_accepted_score: Option<u8>,
+        sla_score: Option<u8>,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.payer = ctx.accounts.user.key();
+        escrow.audit_proof = ctx.accounts.audit_proof.key();
+        escrow.amount = amount;
+        escrow.released = false;
+        escrow.bump = ctx.bumps.escrow;
+        escrow.min_accepted_score = min_accepted_score;
+        escrow.refunded = false;
+        escrow.sla_refunded = false;
+        escrow.sla_score = sla_score;
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        emit!(EscrowFunded {
+            payer: ctx.accounts.user.key(),
+            contract_hash: ctx.accounts.audit_proof.contract_hash,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    // Pay out a verified audit's escrow, or refund it if the proof was revoked
+    // or scored below the payer's minimum
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let audit_proof = &ctx.accounts.audit_proof;
+        
+        require!(!escrow.released, ErrorCode::EscrowAlreadyReleased);
+        
+        let audit_proof_key = escrow.audit_proof;
+        let payer = escrow.payer;
+        let amount = escrow.amount;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            audit_proof_key.as_ref(),
+            payer.as_ref(),
+            &[escrow.bump],
+        ]];
+        
+        let below_min_score = escrow
+            .min_accepted_score
+            .map_or(false, |min_score| audit_proof.audit_score < min_score);
+        
+        let (to_oracle, to_treasury, refunded) = if audit_proof.revoked || below_min_score {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.payer_token_account.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+            
+            (0, 0, amount)
+        } else {
+            require!(audit_proof.verified, ErrorCode::EscrowNotReleasable);
+            
+            let to_oracle = apply_bps(amount, ctx.accounts.config.oracle_fee_bps);
+            let to_treasury = amount - to_oracle;
+            
+            if to_oracle > 0 {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    to: ctx.accounts.oracle_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token::transfer(cpi_ctx, to_oracle)?;
+            }
+            
+            if to_treasury > 0 {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token::transfer(cpi_ctx, to_treasury)?;
+            }
+            
+            (to_oracle, to_treasury, 0)
+        };
+        
+        let contract_hash = ctx.accounts.audit_proof.contract_hash;
+        ctx.accounts.escrow.released = true;
+        ctx.accounts.escrow.refunded = refunded > 0;
+        
+        emit!(EscrowReleased {
+            payer,
+            contract_hash,
+            to_oracle,
+            to_treasury,
+            refunded,
+            below_min_score,
+        });
+        
+        Ok(())
+    }
+
+    // Refund the buyer of a paid-out escrow from the treasury when the audit scored
+    // below the SLA agreed when it was funded, in proportion to the shortfall
+    pub fn sla_refund(ctx: Context<RefundSla>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let audit_score = ctx.accounts.audit_proof.audit_score;
+        
+        require!(escrow.released && !escrow.refunded, ErrorCode::EscrowNotReleasable);
+        require!(!escrow.sla_refunded, ErrorCode::SlaAlreadyRefunded);
+        let sla_score = escrow.sla_score.ok_or(ErrorCode::NoSlaAgreed)?;
+        require!(audit_score < sla_score, ErrorCode::SlaNotBreached);
+        
+        let shortfall = sla_score - audit_score;
+        let amount = (escrow.amount as u128 * shortfall as u128 / sla_score as u128) as u64;
+        
+        if amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.payer_token_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"treasury_authority", &[ctx.bumps.treasury_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+        
+        ctx.accounts.escrow.sla_refunded = true;
+        
+        emit!(SlaRefund {
+            payer: ctx.accounts.escrow.payer,
+            contract_hash: ctx.accounts.audit_proof.contract_hash,
+            amount,
+            shortfall,
+        });
+        
+        Ok(())
+    }
+
+    // Pay for audit with SECURIZZ tokens
+    pub fn pay_for_audit(
+        ctx: Context<PayForAudit>,
+        audit_fee: u64,
+    ) -> Result<()> {
+        require!(audit_fee > 0, ErrorCode::InvalidAmount);
+        
+        // Only the scheduled fee is taken; any surplus stays with the user
+        let required_fee = match ctx.accounts.config.audit_fee {
+            0 => audit_fee,
+            fee => fee,
+        };
+        require!(audit_fee >= required_fee, ErrorCode::InvalidAmount);
+        let overpayment = audit_fee - required_fee;
+        
+        let config = &ctx.accounts.config;
+        let fee_mint = ctx.accounts.user_token_account.mint;
+        let reward_mint = ctx.accounts.reward_pool.mint;
+        require!(ctx.accounts.treasury.mint == fee_mint, ErrorCode::MintMismatch);
+        require!(config.accepts_fee_mint(&fee_mint, &reward_mint), ErrorCode::MintMismatch);
+        
+        // Split the fee between the staking reward pool and the treasury; the pool
+        // only holds the reward mint, so fees in any other mint go to the treasury
+        let to_stakers = if fee_mint == reward_mint {
+            apply_bps(required_fee, config.fee_split_bps)
+        } else {
+            0
+        };
+        let to_treasury = required_fee - to_stakers;
+        
+        if to_stakers > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.reward_pool.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, to_stakers)?;
+        }
+        
+        if to_treasury > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, to_treasury)?;
+        }
+        
+        emit!(FeeSplit {
+            to_stakers,
+            to_treasury,
+        });
+        
+        let staking_stats = &mut ctx.accounts.staking_stats;
+        staking_stats.audit_count = staking_stats.audit_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        staking_stats.record_staker_fees(to_stakers);
+        
+        if overpayment > 0 {
+            emit!(OverpaymentRefunded {
+                user: ctx.accounts.user.key(),
+                amount: overpayment,
+            });
+        }
+        
+        ctx.accounts.audit_proof.fee_mint = fee_mint;
+        
+        emit!(AuditPaid {
+            user: ctx.accounts.user.key(),
+            amount: required_fee,
+            contract_hash: ctx.accounts.audit_proof.contract_hash,
+            mint: fee_mint,
+        });
+        
+        Ok(())
+    }
+
+    // Open a governance proposal for voting
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        voting_period: u64, // in seconds
+        quorum: u64,
+        pass_threshold_bps: u16, // yes share of cast weight required to pass
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let slot = clock.slot;
+        
+        require!(voting_period > 0, ErrorCode::InvalidDuration);
+        validate_bps(pass_threshold_bps)?;
+        
+        // Bond the proposer's stake so spam proposals tie up real tokens
+        let proposer_stake = &mut ctx.accounts.proposer_stake;
+        require!(
+            proposer_stake.amount >= ctx.accounts.config.min_proposal_stake,
+            ErrorCode::InsufficientStake
+        );
+        require!(proposer_stake.bonded_proposal.is_none(), ErrorCode::ProposalBondActive);
+        proposer_stake.bonded_proposal = Some(proposal_id);
+        
+        proposal.proposal_id = proposal_id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.created_at = now;
+        proposal.snapshot_slot = slot;
+        proposal.end_time = now + voting_period as i64;
+        proposal.quorum = quorum;
+        proposal.pass_threshold_bps = pass_threshold_bps;
+        proposal.yes_weight = 0;
+        proposal.no_weight = 0;
+        proposal.abstain_weight = 0;
+        proposal.vote_count = 0;
+        proposal.total_weight = 0;
+        proposal.finalized = false;
+        proposal.passed = false;
+        proposal.proposer_stake = proposer_stake.key();
+        
+        emit!(ProposalCreated {
+            proposal_id,
+            proposer: proposal.proposer,
+            end_time: proposal.end_time,
+        });
+        
+        emit!(ProposalBondLocked {
+            proposal_id,
+            proposer: proposal.proposer,
+            stake_account: proposal.proposer_stake,
+            amount: proposer_stake.amount,
+        });
+        
+        Ok(())
+    }
+
+    // Governance voting with staked tokens
+    pub fn vote_on_proposal(
+        ctx: Context<VoteOnProposal>,
+        proposal_id: u64,
+        vote_weight: u64,
+        vote_type: u8, // VoteType: 0 = no, 1 = yes, 2 = abstain
+    ) -> Result<()> {
+        let vote_account = &mut ctx.accounts.vote_account;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let proposal = &mut ctx.accounts.proposal;
+        let now = current_time()?;
+        let choice = VoteType::try_from(vote_type)?;
+        
+        require!(stake_account.amount >= vote_weight, ErrorCode::InsufficientStake);
+        require!(vote_weight > 0, ErrorCode::InvalidVoteWeight);
+        require!(now < proposal.end_time, ErrorCode::VotingClosed);
+        // Only stake that existed before the proposal's snapshot carries voting power
+        require!(stake_account.staked_slot < proposal.snapshot_slot, ErrorCode::StakeTooRecent);
+        
+        // No single vote may carry more than max_vote_weight_bps of all staked principal
+        let max_vote_weight_bps = ctx.accounts.config.max_vote_weight_bps;
+        let requested_weight = vote_weight;
+        let vote_weight = if max_vote_weight_bps > 0 {
+            vote_weight.min(apply_bps(ctx.accounts.staking_stats.total_staked, max_vote_weight_bps))
+        } else {
+            vote_weight
+        };
+        require!(vote_weight > 0, ErrorCode::InvalidVoteWeight);
+        
+        vote_account.proposal_id = proposal_id;
+        vote_account.voter = ctx.accounts.voter.key();
+        vote_account.vote_weight = vote_weight;
+        vote_account.vote_type = vote_type;
+        vote_account.voted_at = now;
+        vote_account.bump = ctx.bumps.vote_account;
+        
+        // Bank rewards earned so far before this vote changes the governance bonus
+        let settled_before = stake_account.accrued_rewards;
+        let rewards = stake_account.settle_rewards(now, &ctx.accounts.config, &ctx.accounts.staking_stats);
+        emit!(RewardsSettled {
+            user: stake_account.user,
+            settled: rewards.claimable.saturating_sub(settled_before),
+        });
+        
+        // Keep the stake collateralizing this vote until the latest proposal it backs ends
+        stake_account.locked_until = stake_account.locked_until.max(proposal.end_time);
+        stake_account.votes_cast += 1;
+        
+        // Abstentions count toward quorum through total_weight but not toward the outcome
+        match choice {
+            VoteType::Yes => {
+                proposal.yes_weight = proposal.yes_weight.checked_add(vote_weight).ok_or(ErrorCode::MathOverflow)?;
+            }
+            VoteType::No => {
+                proposal.no_weight = proposal.no_weight.checked_add(vote_weight).ok_or(ErrorCode::MathOverflow)?;
+            }
+            VoteType::Abstain => {
+                proposal.abstain_weight = proposal.abstain_weight.checked_add(vote_weight).ok_or(ErrorCode::MathOverflow)?;
+            }
+        }
+        proposal.vote_count = proposal.vote_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        proposal.total_weight = proposal.total_weight.checked_add(vote_weight).ok_or(ErrorCode::MathOverflow)?;
+        
+        assert_rent_exempt(&vote_account.to_account_info())?;
+        
+        emit!(VoteCast {
+            proposal_id,
+            voter: ctx.accounts.voter.key(),
+            requested_weight,
+            vote_weight,
+            vote_type,
+        });
+        
+        emit!(VoteRecorded {
+            proposal_id,
+            vote_count: proposal.vote_count,
+            total_weight: proposal.total_weight,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+            abstain_weight: proposal.abstain_weight,
+        });
+        
+        Ok(())
+    }
+
+    // Close voting and record the outcome
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        let now = current_time()?;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(now >= proposal.end_time, ErrorCode::VotingNotEnded);
+        require!(!proposal.finalized, ErrorCode::ProposalAlreadyFinalized);
+        
+        let yes_weight = proposal.yes_weight as u128;
+        let total_weight = yes_weight + proposal.no_weight as u128;
+        
+        // Quorum counts abstentions; the pass threshold only weighs yes against no.
+        // With no decisive votes the threshold comparison is 0 >= 0, so it never
+        // counts as met, and an exact yes/no tie always fails.
+        let quorum_met = total_weight + proposal.abstain_weight as u128 >= proposal.quorum as u128;
+        let threshold_met = total_weight > 0
+            && yes_weight * 10_000 >= total_weight * proposal.pass_threshold_bps as u128;
+        let tied = proposal.yes_weight == proposal.no_weight;
+        let yes_bps = if total_weight == 0 { 0 } else { (yes_weight * 10_000 / total_weight) as u16 };
+        
+        proposal.finalized = true;
+        proposal.passed = quorum_met && threshold_met && !tied;
+        
+        // Release the proposer's bond
+        let proposer_stake = &mut ctx.accounts.proposer_stake;
+        if proposer_stake.bonded_proposal == Some(proposal.proposal_id) {
+            proposer_stake.bonded_proposal = None;
+        }
+        
+        emit!(ProposalFinalized {
+            proposal_id: proposal.proposal_id,
+            passed: proposal.passed,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+            abstain_weight: proposal.abstain_weight,
+            yes_bps,
+        });
+        
+        Ok(())
+    }
+
+    // Set the treasury signer set; withdrawals need `threshold` of them to approve
+    pub fn initialize_treasury_config(
+        ctx: Context<InitializeTreasuryConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(signers.len() <= MAX_TREASURY_SIGNERS, ErrorCode::TooManySigners);
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            ErrorCode::InvalidThreshold
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), ErrorCode::DuplicateSigner);
+        }
+        
+        let treasury_config = &mut ctx.accounts.treasury_config;
+        treasury_config.signers = signers;
+        treasury_config.threshold = threshold;
+        treasury_config.withdrawal_count = 0;
+        treasury_config.bump = ctx.bumps.treasury_config;
+        
+        Ok(())
+    }
+
+    // Open a treasury withdrawal for the other signers to approve
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        amount: u64,
+        recipient: Pubkey, // destination token account
+    ) -> Result<()> {
+        let treasury_config = &mut ctx.accounts.treasury_config;
+        let proposer = ctx.accounts.proposer.key();
+        
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(treasury_config.signers.contains(&proposer), ErrorCode::Unauthorized);
+        
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.withdrawal_id = treasury_config.withdrawal_count;
+        withdrawal.proposer = proposer;
+        withdrawal.amount = amount;
+        withdrawal.recipient = recipient;
+        withdrawal.approvals = Vec::new();
+        withdrawal.executed = false;
+        withdrawal.bump = ctx.bumps.withdrawal;
+        
+        treasury_config.withdrawal_count += 1;
+        
+        emit!(WithdrawalProposed {
+            withdrawal_id: withdrawal.withdrawal_id,
+            proposer,
+            amount,
+            recipient,
+        });
+        
+        Ok(())
+    }
+
+    // Approve a withdrawal; the approval that reaches the threshold executes it
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        let treasury_config = &ctx.accounts.treasury_config;
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        let signer = ctx.accounts.signer.key();
+        
+        require!(treasury_config.signers.contains(&signer), ErrorCode::Unauthorized);
+        require!(!withdrawal.executed, ErrorCode::WithdrawalAlreadyExecuted);
+        require!(!withdrawal.approvals.contains(&signer), ErrorCode::AlreadyApproved);
+        
+        withdrawal.approvals.push(signer);
+        
+        emit!(WithdrawalApproved {
+            withdrawal_id: withdrawal.withdrawal_id,
+            signer,
+            approvals: withdrawal.approvals.len() as u8,
+        });
+        
+        if withdrawal.approvals.len() < treasury_config.threshold as usize {
+            return Ok(());
+        }
+        
+        withdrawal.executed = true;
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.treasury.to_account_info(),
+            to: ctx.accounts.recipient.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury_authority", &[ctx.bumps.treasury_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, withdrawal.amount)?;
+        
+        emit!(WithdrawalExecuted {
+            withdrawal_id: withdrawal.withdrawal_id,
+            amount: withdrawal.amount,
+            recipient: withdrawal.recipient,
+        });
+        
+        Ok(())
+    }
+}
+
+pub const MAX_USER_STAKES: usize = 8;
+pub const MAX_TREASURY_SIGNERS: usize = 10;
+pub const BASE_DAILY_RATE_BPS: u16 = 100; // 1% of principal per day
+pub const FEE_SHARE_SCALE: u128 = 1_000_000_000_000; // precision of StakingStats.fees_per_share
+// (minimum lock duration, daily rate) in ascending order; longer locks earn more
+pub const LOCK_TIERS: [(u64, u16); 3] = [
+    (0, BASE_DAILY_RATE_BPS),
+    (90 * SECONDS_PER_DAY, 125),
+    (365 * SECONDS_PER_DAY, 150),
+];
+
+// Daily reward rate for a lock of `duration` seconds
+pub fn lock_tier_rate_bps(duration: u64) -> u16 {
+    LOCK_TIERS
+        .iter()
+        .rev()
+        .find(|(min_duration, _)| duration >= *min_duration)
+        .map_or(BASE_DAILY_RATE_BPS, |(_, rate_bps)| *rate_bps)
+}
+
+// Total rewards accrued between `staked_at` and `now` at `rate_bps` per day. Kept
+// as a single closed-form expression (no per-day loop) so compute cost is constant
+// no matter how long the stake has been held; widened to u128 so large principals
+// or durations cannot overflow before the division.
+pub fn accrued_rewards(amount: u64, rate_bps: u16, staked_at: i64, now: i64, round_up: bool) -> u64 {
+    let elapsed = now.saturating_sub(staked_at).max(0) as u128;
+    let numerator = amount as u128 * rate_bps as u128 * elapsed;
+    let denominator = 10_000u128 * SECONDS_PER_DAY as u128;
+    let rewards = if round_up {
+        (numerator + denominator - 1) / denominator
+    } else {
+        numerator / denominator
+    };
+    rewards.min(u64::MAX as u128) as u64
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitializeToken<'info> {
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = authority,
+        mint::freeze_authority = authority,
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenMeta::INIT_SPACE,
+        seeds = [b"token_meta", mint.key().as_ref()],
+        bump
+    )]
+    pub token_meta: Account<'info, TokenMeta>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStakingStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingStats::INIT_SPACE,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", user.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStakeIndex::INIT_SPACE,
+        seeds = [b"stake_index", user.key().as_ref()],
+        bump
+    )]
+    pub stake_index: Account<'info, UserStakeIndex>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.staking_pool @ ErrorCode::Unauthorized)]
+    pub staking_pool: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"position", stake_account.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = staking_authority
+    )]
+    pub position_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = position_mint,
+        associated_token::authority = user
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.reward_pool @ ErrorCode::Unauthorized)]
+    pub reward_pool: Account<'info, TokenAccount>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(
+        constraint = auditor_badge.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = auditor_badge.mint == config.badge_mint @ ErrorCode::Unauthorized
+    )]
+    pub auditor_badge: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = destination.mint == reward_pool.mint @ ErrorCode::MintMismatch
+    )]
+    pub destination: Option<Account<'info, TokenAccount>>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewClaim<'info> {
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+}
+
+#[derive(Accounts)]
+pub struct ReadStakes<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        close = user
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_index", stake_account.user.as_ref()],
+        bump
+    )]
+    pub stake_index: Account<'info, UserStakeIndex>,
+    #[account(mut, address = stake_account.position_mint @ ErrorCode::Unauthorized)]
+    pub position_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = position_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.mint == position_mint.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.staking_pool @ ErrorCode::Unauthorized)]
+    pub staking_pool: Account<'info, TokenAccount>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + WithdrawalRequest::INIT_SPACE,
+        seeds = [b"withdrawal_request", stake_account.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    #[account(address = stake_account.position_mint @ ErrorCode::Unauthorized)]
+    pub position_mint: Account<'info, Mint>,
+    #[account(
+        constraint = position_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.mint == position_mint.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"withdrawal_request", withdrawal_request.stake_account.as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = user @ ErrorCode::Unauthorized,
+        close = user
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.staking_pool @ ErrorCode::Unauthorized)]
+    pub staking_pool: Account<'info, TokenAccount>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferStake<'info> {
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_index", user.key().as_ref()],
+        bump
+    )]
+    pub from_index: Account<'info, UserStakeIndex>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStakeIndex::INIT_SPACE,
+        seeds = [b"stake_index", new_owner.as_ref()],
+        bump
+    )]
+    pub to_index: Account<'info, UserStakeIndex>,
+    #[account(address = stake_account.position_mint @ ErrorCode::Unauthorized)]
+    pub position_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = position_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.mint == position_mint.key() @ ErrorCode::Unauthorized,
+        constraint = position_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = new_owner_position_account.owner == new_owner @ ErrorCode::Unauthorized,
+        constraint = new_owner_position_account.mint == position_mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub new_owner_position_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeDelegation<'info> {
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AutoRestake<'info> {
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, address = config.reward_pool @ ErrorCode::Unauthorized)]
+    pub reward_pool: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == keeper.key() @ ErrorCode::Unauthorized
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub keeper: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OracleReward<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+    #[account(
+        mut,
+        constraint = oracle_token_account.owner == audit_proof.oracle @ ErrorCode::Unauthorized
+    )]
+    pub oracle_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub mint: Option<Account<'info, Mint>>,
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+    /// CHECK: This is the treasury authority PDA
+    #[account(
+        seeds = [b"treasury_authority"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefillRewardPool<'info> {
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ ErrorCode::Unauthorized
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.reward_pool @ ErrorCode::Unauthorized)]
+    pub reward_pool: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOracle<'info> {
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        address = config.staking_pool @ ErrorCode::Unauthorized,
+        constraint = staking_pool.mint == mint.key() @ ErrorCode::Unauthorized
+    )]
+    pub staking_pool: Account<'info, TokenAccount>,
+    #[account(mut, address = config.reward_pool @ ErrorCode::Unauthorized)]
+    pub reward_pool: Account<'info, TokenAccount>,
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    /// CHECK: This is the staking authority PDA
+    #[account(
+        seeds = [b"staking_authority"],
+        bump
+    )]
+    pub staking_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SetStakeHold<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", user.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EscrowAuditPayment<'info> {
+    pub audit_proof: Account<'info, AuditProof>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", audit_proof.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"escrow_vault", escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    #[account(address = escrow.audit_proof @ ErrorCode::Unauthorized)]
+    pub audit_proof: Account<'info, AuditProof>,
+    #[account(
+        mut,
+        seeds = [b"escrow", audit_proof.key().as_ref(), escrow.payer.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"escrow_vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = oracle_token_account.owner == audit_proof.oracle @ ErrorCode::Unauthorized
+    )]
+    pub oracle_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer @ ErrorCode::Unauthorized
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefundSla<'info> {
+    #[account(address = escrow.audit_proof @ ErrorCode::Unauthorized)]
+    pub audit_proof: Account<'info, AuditProof>,
+    #[account(
+        mut,
+        seeds = [b"escrow", audit_proof.key().as_ref(), escrow.payer.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        address = config.treasury @ ErrorCode::Unauthorized,
+        constraint = treasury.owner == treasury_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    /// CHECK: This is the treasury authority PDA
+    #[account(
+        seeds = [b"treasury_authority"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer @ ErrorCode::Unauthorized
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PayForAudit<'info> {
+    #[account(mut)]
+    pub audit_proof: Account<'info, AuditProof>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    // config.treasury, or for other fee mints any account the treasury authority owns
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury
+            || treasury.owner == treasury_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    /// CHECK: This is the treasury authority PDA
+    #[account(
+        seeds = [b"treasury_authority"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(mut, address = config.reward_pool @ ErrorCode::Unauthorized)]
+    pub reward_pool: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        constraint = proposer_stake.user == proposer.key() @ ErrorCode::Unauthorized
+    )]
+    pub proposer_stake: Account<'info, StakeAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct VoteOnProposal<'info> {
+    #[account(
+        mut,
+        constraint = stake_account.voting_authority() == voter.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    // Keyed by stake rather than voter so delegating or transferring a stake
+    // can't give it a second vote
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteAccount::INIT_SPACE,
+        seeds = [b"vote", proposal_id.to_le_bytes().as_ref(), stake_account.key().as_ref()],
+        bump
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"staking_stats"],
+        bump
+    )]
+    pub staking_stats: Account<'info, StakingStats>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, address = proposal.proposer_stake @ ErrorCode::Unauthorized)]
+    pub proposer_stake: Account<'info, StakeAccount>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TreasuryConfig::INIT_SPACE,
+        seeds = [b"treasury_config"],
+        bump
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_config"],
+        bump = treasury_config.bump
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + WithdrawalProposal::INIT_SPACE,
+        seeds = [b"withdrawal", treasury_config.withdrawal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub withdrawal: Account<'info, WithdrawalProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    #[account(
+        seeds = [b"treasury_config"],
+        bump = treasury_config.bump
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+    #[account(
+        mut,
+        seeds = [b"withdrawal", withdrawal.withdrawal_id.to_le_bytes().as_ref()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, WithdrawalProposal>,
+    #[account(
+        mut,
+        address = config.treasury @ ErrorCode::Unauthorized,
+        constraint = treasury.owner == treasury_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, address = withdrawal.recipient @ ErrorCode::Unauthorized)]
+    pub recipient: Account<'info, TokenAccount>,
+    /// CHECK: This is the treasury authority PDA
+    #[account(
+        seeds = [b"treasury_authority"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenMeta {
+    pub mint: Pubkey,
+    pub authority: Pubkey, // signer of initialize_token
+    pub decimals: u8,
+    pub initialized_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub duration: u64,
+    pub staked_at: i64,
+    pub staked_slot: u64,
+    pub unlock_time: i64,
+    pub rewards_claimed: u64, // paid out or settled into accrued_rewards
+    pub locked_until: i64, // end of the latest proposal this stake voted on
+    pub grace_period: u64,
+    pub last_claim_time: i64,
+    pub audit_count_snapshot: u64, // StakingStats.audit_count when staked
+    pub cliff_seconds: u64,
+    pub votes_cast: u64, // governance votes cast while staked
+    pub position_mint: Pubkey, // receipt token; its holder may withdraw the stake
+    pub bonded_proposal: Option<u64>, // proposal this stake backs as proposer bond
+    pub delegate: Option<Pubkey>, // votes with this stake instead of the owner
+    pub delegation_changed_at: i64,
+    pub bump: u8,
+    pub accrued_rewards: u64, // settled but not yet claimed
+    pub reward_rate_bps: u16, // daily rate from the lock tier, 0 = base rate
+    pub held: bool, // compliance hold set by the admin, blocks claims
+    pub fees_per_share_snapshot: u128, // StakingStats.fees_per_share when accrual last restarted
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingStats {
+    pub audit_count: u64, // audits paid since launch
+    pub total_staked: u64, // principal currently held across all stakes
+    pub solvency_ratio: u16, // bps of estimated pending rewards the pool covered at the last claim
+    pub fees_per_share: u128, // staker audit fees per staked token since launch, scaled by FEE_SHARE_SCALE
+}
+
+impl StakingStats {
+    // Spreads audit fees paid into the reward pool over the principal staked right now
+    pub fn record_staker_fees(&mut self, amount: u64) {
+        if self.total_staked > 0 {
+            self.fees_per_share = self
+                .fees_per_share
+                .saturating_add(amount as u128 * FEE_SHARE_SCALE / self.total_staked as u128);
+        }
+    }
+}
+
+// Lifetime reward accrual for a stake, split by source
+#[derive(Clone, Copy, Default)]
+pub struct RewardBreakdown {
+    pub time_rewards: u64,     // from the lock tier's daily rate
+    pub activity_rewards: u64, // from audits paid while staked
+    pub governance_rewards: u64, // from governance votes cast while staked
+    pub capped: bool,          // total was limited by the daily or lifetime cap
+    pub claimable: u64,        // accrued total not yet claimed
+}
+
+impl StakeAccount {
+    // The delegate if there is one, otherwise the owner
+    pub fn voting_authority(&self) -> Pubkey {
+        self.delegate.unwrap_or(self.user)
+    }
+
+    // Delegating or revoking restarts the cooldown so voting power can't hop between keys
+    pub fn check_delegation_cooldown(&self, now: i64, config: &ProgramConfig) -> Result<()> {
+        if self.delegation_changed_at > 0 {
+            let ready_at = self.delegation_changed_at
+                .checked_add(config.delegation_cooldown as i64)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(now >= ready_at, ErrorCode::DelegationCooldown);
+        }
+        Ok(())
+    }
+
+    // Rewards accrue from the end of the cliff rather than from staked_at
+    pub fn accrual_start(&self) -> i64 {
+        self.staked_at.saturating_add(self.cliff_seconds as i64)
+    }
+
+    // Stakes opened before lock tiers were introduced earn the base rate
+    pub fn daily_rate_bps(&self) -> u16 {
+        if self.reward_rate_bps == 0 { BASE_DAILY_RATE_BPS } else { self.reward_rate_bps }
+    }
+
+    // Rewards accrued at `now` that have not been claimed yet
+    pub fn claimable_rewards(&self, now: i64, config: &ProgramConfig, stats: &StakingStats) -> RewardBreakdown {
+        let accrual_start = self.accrual_start();
+        if now < accrual_start {
+            return RewardBreakdown {
+                claimable: self.accrued_rewards,
+                ..RewardBreakdown::default()
+            };
+        }
+        
+        let time_rewards = accrued_rewards(self.amount, self.daily_rate_bps(), accrual_start, now, config.round_up_rewards);
+        
+        // This stake's pro-rata share of the audit fees paid into the pool while it was
+        // staked, so the bonus can never exceed what audits actually brought in
+        let fees_earned = self.amount as u128
+            * stats.fees_per_share.saturating_sub(self.fees_per_share_snapshot)
+            / FEE_SHARE_SCALE;
+        let activity_rewards = apply_bps(fees_earned.min(u64::MAX as u128) as u64, config.activity_bonus_bps);
+        
+        let counted_votes = self.votes_cast.min(config.max_bonus_votes);
+        let governance_rewards = apply_bps(self.amount, config.governance_bonus_bps).saturating_mul(counted_votes);
+        
+        let mut accrued = time_rewards.saturating_add(activity_rewards).saturating_add(governance_rewards);
+        let mut capped = false;
+        
+        if config.daily_reward_cap > 0 {
+            let days_elapsed = now.saturating_sub(accrual_start).max(0) as u64 / SECONDS_PER_DAY;
+            let cap = config.daily_reward_cap.saturating_mul(days_elapsed);
+            if accrued > cap {
+                accrued = cap;
+                capped = true;
+            }
+        }
+        
+        if config.max_total_reward_multiple > 0 {
+            let lifetime_cap = (self.amount as u128 * config.max_total_reward_multiple as u128 / 100)
+                .min(u64::MAX as u128) as u64;
+            if accrued > lifetime_cap {
+                accrued = lifetime_cap;
+                capped = true;
+            }
+        }
+        
+        RewardBreakdown {
+            time_rewards,
+            activity_rewards,
+            governance_rewards,
+            capped,
+            claimable: accrued.saturating_sub(self.rewards_claimed).saturating_add(self.accrued_rewards),
+        }
+    }
+
+    // Removes principal after settling what it earned, then restarts accrual from now
+    // for the remainder so the smaller amount is not applied to time already paid for
+    pub fn reduce_principal(&mut self, amount: u64, now: i64, config: &ProgramConfig, stats: &StakingStats) {
+        self.settle_rewards(now, config, stats);
+        self.amount -= amount;
+        self.restart_accrual(now, stats);
+    }
+
+    // Settles at the current rate, then switches to `rate_bps` from now on
+    pub fn change_rate(&mut self, rate_bps: u16, now: i64, config: &ProgramConfig, stats: &StakingStats) {
+        self.settle_rewards(now, config, stats);
+        self.reward_rate_bps = rate_bps;
+        self.restart_accrual(now, stats);
+    }
+
+    // After a settlement, accrual is recomputed from `now` so changed terms are
+    // not applied to time already paid for; nothing has accrued during the cliff
+    pub fn restart_accrual(&mut self, now: i64, stats: &StakingStats) {
+        if now >= self.accrual_start() {
+            self.staked_at = now.saturating_sub(self.cliff_seconds as i64);
+            self.rewards_claimed = 0;
+            self.audit_count_snapshot = stats.audit_count;
+            self.fees_per_share_snapshot = stats.fees_per_share;
+            self.votes_cast = 0;
+        }
+    }
+
+    // Moves rewards earned since the last settlement into accrued_rewards without
+    // transferring anything; claimable_rewards reports the same total before and after
+    pub fn settle_rewards(&mut self, now: i64, config: &ProgramConfig, stats: &StakingStats) -> RewardBreakdown {
+        let rewards = self.claimable_rewards(now, config, stats);
+        let settled = rewards.claimable.saturating_sub(self.accrued_rewards);
+        self.rewards_claimed = self.rewards_claimed.saturating_add(settled);
+        self.accrued_rewards = rewards.claimable;
+        rewards
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub created_at: i64,
+    pub snapshot_slot: u64, // stakes created at or after this slot cannot vote
+    pub end_time: i64,
+    pub quorum: u64, // minimum total weight cast
+    pub pass_threshold_bps: u16,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub vote_count: u64,
+    pub total_weight: u64,
+    pub finalized: bool,
+    pub passed: bool,
+    pub proposer_stake: Pubkey, // bonded until finalization
+    pub abstain_weight: u64, // counts toward quorum only
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserStakeIndex {
+    pub user: Pubkey,
+    #[max_len(MAX_USER_STAKES)]
+    pub stakes: Vec<Pubkey>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryConfig {
+    #[max_len(MAX_TREASURY_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8, // distinct approvals needed to execute a withdrawal
+    pub withdrawal_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalProposal {
+    pub withdrawal_id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    #[max_len(MAX_TREASURY_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub payer: Pubkey,
+    pub audit_proof: Pubkey,
+    pub amount: u64,
+    pub released: bool,
+    pub bump: u8,
+    pub min_accepted_score: Option<u8>, // refund instead of paying below this audit_score
+    pub refunded: bool, // released back to the payer rather than paid out
+    pub sla_refunded: bool,
+    pub sla_score: Option<u8>, // audit_score promised to the payer, if any
+}
+
+// Principal taken out of a stake, payable once ready_at has passed
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    pub stake_account: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub ready_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteAccount {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub vote_weight: u64,
+    pub vote_type: u8, // VoteType
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VoteType {
+    No,
+    Yes,
+    Abstain,
+}
+
+impl TryFrom<u8> for VoteType {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(VoteType::No),
+            1 => Ok(VoteType::Yes),
+            2 => Ok(VoteType::Abstain),
+            _ => err!(ErrorCode::InvalidVoteType),
+        }
+    }
+}
+
+#[event]
+pub struct TokenInitialized {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub decimals: u8,
+}
+
+#[event]
 pub struct TokensStaked {
     pub user: Pubkey,
     pub amount: u64,
@@ -270,11 +2378,115 @@ pub struct TokensStaked {
     pub unlock_time: i64,
 }
 
+#[event]
+pub struct RewardsSettled {
+    pub user: Pubkey,
+    pub settled: u64,
+}
+
 #[event]
 pub struct RewardsClaimed {
     pub user: Pubkey,
     pub amount: u64,
     pub total_claimed: u64,
+    pub capped: bool,
+    pub time_rewards: u64, // lifetime accrual from the daily rate
+    pub activity_rewards: u64, // lifetime accrual from audit activity
+    pub governance_rewards: u64, // lifetime accrual from votes cast
+    pub badge_bonus: u64, // included in amount, on top of accrued rewards
+}
+
+#[event]
+pub struct ClaimablePreview {
+    pub user: Pubkey,
+    pub claimable: u64,
+    pub next_claim_time: i64,
+}
+
+#[event]
+pub struct TotalClaimable {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionMinted {
+    pub stake_account: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct PositionBurned {
+    pub stake_account: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub stake_account: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub ready_at: i64,
+}
+
+#[event]
+pub struct WithdrawalClaimed {
+    pub stake_account: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VotesDelegated {
+    pub stake_account: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct RewardRateReduced {
+    pub factor: u16, // bps of the claim actually paid
+}
+
+#[event]
+pub struct LockExtended {
+    pub user: Pubkey,
+    pub new_unlock_time: i64,
+    pub new_rate: u16, // daily reward rate in bps
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub stake_account: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct StakeTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AutoRestaked {
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub fee: u64,
+}
+
+#[event]
+pub struct ClaimTooSoon {
+    pub next_time: i64,
 }
 
 #[event]
@@ -282,28 +2494,141 @@ pub struct AuditPaid {
     pub user: Pubkey,
     pub amount: u64,
     pub contract_hash: [u8; 32],
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct OracleRewarded {
+    pub oracle: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub amount: u64,
+    pub source: RewardSource,
+}
+
+#[event]
+pub struct RewardPoolRefilled {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct SlashDistributed {
+    pub oracle: Pubkey,
+    pub burned: u64,
+    pub to_treasury: u64,
+    pub to_rewards: u64,
+}
+
+#[event]
+pub struct StakeHeld {
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct StakeReleased {
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct EscrowFunded {
+    pub payer: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowReleased {
+    pub payer: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub to_oracle: u64,
+    pub to_treasury: u64,
+    pub refunded: u64,
+    pub below_min_score: bool,
+}
+
+#[event]
+pub struct SlaRefund {
+    pub payer: Pubkey,
+    pub contract_hash: [u8; 32],
+    pub amount: u64,
+    pub shortfall: u8, // SLA score minus audit_score
+}
+
+#[event]
+pub struct OverpaymentRefunded {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeeSplit {
+    pub to_stakers: u64,
+    pub to_treasury: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct ProposalBondLocked {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
 pub struct VoteCast {
     pub proposal_id: u64,
     pub voter: Pubkey,
-    pub vote_weight: u64,
-    pub support: bool,
-}
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid amount")]
-    InvalidAmount,
-    #[msg("Invalid duration")]
-    InvalidDuration,
-    #[msg("Stake not unlocked")]
-    StakeNotUnlocked,
-    #[msg("No rewards available")]
-    NoRewardsAvailable,
-    #[msg("Insufficient stake")]
-    InsufficientStake,
-    #[msg("Invalid vote weight")]
-    InvalidVoteWeight,
+    pub requested_weight: u64,
+    pub vote_weight: u64, // after the max_vote_weight_bps cap
+    pub vote_type: u8,
+}
+
+#[event]
+pub struct VoteRecorded {
+    pub proposal_id: u64,
+    pub vote_count: u64,
+    pub total_weight: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub abstain_weight: u64,
+}
+
+#[event]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub passed: bool,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub abstain_weight: u64,
+    pub yes_bps: u16, // yes share of yes + no weight
+}
+
+#[event]
+pub struct WithdrawalProposed {
+    pub withdrawal_id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalApproved {
+    pub withdrawal_id: u64,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct WithdrawalExecuted {
+    pub withdrawal_id: u64,
+    pub amount: u64,
+    pub recipient: Pubkey,
 }
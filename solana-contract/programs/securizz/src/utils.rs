@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// Portion of `amount` represented by `bps` basis points, rounded down
+pub fn apply_bps(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+// Every configurable basis-point value must stay within 0-100%
+pub fn validate_bps(value: u16) -> Result<()> {
+    require!(value <= 10_000, ErrorCode::InvalidBps);
+    Ok(())
+}
+
+// `init` funds new accounts for rent exemption; checked explicitly after the
+// handler has written its data in case the account was resized
+pub fn assert_rent_exempt(account: &AccountInfo) -> Result<()> {
+    let rent = Rent::get()?;
+    require!(
+        rent.is_exempt(account.lamports(), account.data_len()),
+        ErrorCode::NotRentExempt
+    );
+    Ok(())
+}
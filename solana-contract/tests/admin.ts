@@ -0,0 +1,213 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { DAY, FEE_SPLIT_BPS, TestEnv } from "./helpers";
+
+describe("admin", () => {
+  let env: TestEnv;
+
+  beforeEach(async () => {
+    env = await TestEnv.start();
+  });
+
+  describe("admin handover", () => {
+    let newAdmin: Keypair;
+
+    beforeEach(async () => {
+      newAdmin = Keypair.generate();
+      await env.fund(newAdmin);
+    });
+
+    function proposeAdmin(signer = env.admin) {
+      return env.program.methods
+        .proposeAdmin(newAdmin.publicKey)
+        .accounts({ config: env.config, admin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    function acceptAdmin(signer = newAdmin) {
+      return env.program.methods
+        .acceptAdmin()
+        .accounts({ config: env.config, newAdmin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("hands over immediately without a timelock", async () => {
+      await proposeAdmin();
+      await acceptAdmin();
+
+      const config = await env.fetchConfig();
+      expect(config.admin.equals(newAdmin.publicKey)).to.be.true;
+      expect(config.pendingAdmin).to.be.null;
+    });
+
+    it("waits out the timelock delay before the new admin can accept", async () => {
+      await env.updateConfig({ timelockDelay: new BN(DAY) });
+      await proposeAdmin();
+
+      await env.expectError(acceptAdmin(), "TimelockNotElapsed");
+
+      await env.warp(DAY);
+      await acceptAdmin();
+      expect((await env.fetchConfig()).admin.equals(newAdmin.publicKey)).to.be.true;
+    });
+
+    it("rejects proposals from anyone but the admin", async () => {
+      await env.expectError(proposeAdmin(newAdmin), "Unauthorized");
+    });
+
+    it("rejects acceptance by anyone but the pending admin", async () => {
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+      await proposeAdmin();
+
+      await env.expectError(acceptAdmin(outsider), "Unauthorized");
+    });
+  });
+
+  describe("get_config", () => {
+    it("reports the current config", async () => {
+      const [snapshot] = await env.events(
+        env.program.methods.getConfig().accounts({ config: env.config }),
+        "ConfigSnapshot"
+      );
+
+      expect(snapshot.admin.equals(env.admin.publicKey)).to.be.true;
+      expect(snapshot.feeSplitBps).to.equal(FEE_SPLIT_BPS);
+      expect(snapshot.treasury.equals(env.treasury)).to.be.true;
+    });
+  });
+
+  describe("update_config", () => {
+    const bpsSettings = [
+      "feeSplitBps",
+      "activityBonusBps",
+      "oracleFeeBps",
+      "governanceBonusBps",
+      "decayBpsPerDay",
+      "badgeBonusBps",
+      "maxVoteWeightBps",
+      "closeFeeBps",
+    ];
+
+    for (const setting of bpsSettings) {
+      it(`rejects ${setting} above 100%`, async () => {
+        await env.expectError(env.updateConfig({ [setting]: 10_001 }), "InvalidBps");
+      });
+    }
+
+    it("rejects a fee split above 100% at initialization", async () => {
+      await env.expectError(TestEnv.start(10_001), "InvalidBps");
+    });
+  });
+
+  describe("freeze_rewards", () => {
+    function freezeRewards(frozen: boolean, signer = env.admin) {
+      return env.program.methods
+        .freezeRewards(frozen)
+        .accounts({ config: env.config, admin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("freezes and unfreezes rewards", async () => {
+      await freezeRewards(true);
+      expect((await env.fetchConfig()).rewardsFrozen).to.be.true;
+
+      await freezeRewards(false);
+      expect((await env.fetchConfig()).rewardsFrozen).to.be.false;
+    });
+
+    it("rejects callers other than the admin", async () => {
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+
+      await env.expectError(freezeRewards(true, outsider), "Unauthorized");
+    });
+
+    it("has to go through the action queue once a timelock is set", async () => {
+      await env.updateConfig({ timelockDelay: new BN(DAY) });
+
+      await env.expectError(freezeRewards(true), "TimelockRequired");
+    });
+  });
+
+  describe("action queue", () => {
+    const FREEZE_REWARDS = 1;
+
+    beforeEach(async () => {
+      await env.updateConfig({ timelockDelay: new BN(DAY) });
+    });
+
+    async function queueAction(actionType = FREEZE_REWARDS, params = Buffer.from([1]), signer = env.admin) {
+      const actionId = (await env.fetchConfig()).queuedActionCount.toNumber();
+      await env.program.methods
+        .queueAction(actionType, params)
+        .accounts({
+          queuedAction: env.queuedAction(actionId),
+          config: env.config,
+          admin: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+      return actionId;
+    }
+
+    function executeAction(actionId: number, signer = env.admin) {
+      return env.program.methods
+        .executeAction()
+        .accounts({ queuedAction: env.queuedAction(actionId), config: env.config, admin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    function cancelAction(actionId: number, signer = env.admin) {
+      return env.program.methods
+        .cancelAction()
+        .accounts({ queuedAction: env.queuedAction(actionId), config: env.config, admin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("runs a queued action once its delay has passed", async () => {
+      const actionId = await queueAction();
+
+      await env.expectError(executeAction(actionId), "TimelockNotElapsed");
+
+      await env.warp(DAY);
+      await executeAction(actionId);
+      expect((await env.fetchConfig()).rewardsFrozen).to.be.true;
+      expect(await env.exists(env.queuedAction(actionId))).to.be.false;
+    });
+
+    it("drops a cancelled action without running it", async () => {
+      const actionId = await queueAction();
+
+      await cancelAction(actionId);
+      await env.warp(DAY);
+
+      expect(await env.exists(env.queuedAction(actionId))).to.be.false;
+      expect((await env.fetchConfig()).rewardsFrozen).to.be.false;
+    });
+
+    it("rejects malformed actions before the delay starts", async () => {
+      await env.expectError(queueAction(7, Buffer.from([])), "InvalidActionType");
+      await env.expectError(queueAction(FREEZE_REWARDS, Buffer.from([])), "InvalidActionParams");
+    });
+
+    it("rejects queueing, executing and cancelling by anyone but the admin", async () => {
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+      const actionId = await queueAction();
+      await env.warp(DAY);
+
+      await env.expectError(queueAction(FREEZE_REWARDS, Buffer.from([1]), outsider), "Unauthorized");
+      await env.expectError(executeAction(actionId, outsider), "Unauthorized");
+      await env.expectError(cancelAction(actionId, outsider), "Unauthorized");
+    });
+  });
+});
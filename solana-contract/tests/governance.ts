@@ -0,0 +1,177 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { DAY, TestEnv, User, VOTE_NO, VOTE_YES } from "./helpers";
+
+describe("governance", () => {
+  let env: TestEnv;
+  let nextProposalId = 1;
+
+  before(async () => {
+    env = await TestEnv.start();
+  });
+
+  // Each proposal bonds its proposer's stake until it finalizes, so every one gets a fresh proposer
+  async function createProposal(passThresholdBps = 5_000): Promise<number> {
+    const proposalId = nextProposalId++;
+    const proposer = await env.newUser();
+    const proposerStake = await env.stake(proposer, 1_000_000);
+    await env.program.methods
+      .createProposal(new BN(proposalId), new BN(DAY), new BN(0), passThresholdBps)
+      .accounts({
+        proposal: env.proposalAddress(proposalId),
+        proposerStake,
+        config: env.config,
+        proposer: proposer.keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer.keypair])
+      .rpc();
+    return proposalId;
+  }
+
+  function vote(proposalId: number, stakeAccount: PublicKey, voter: User, weight = 1_000, voteType = VOTE_YES) {
+    return env.program.methods
+      .voteOnProposal(new BN(proposalId), new BN(weight), voteType)
+      .accounts({
+        stakeAccount,
+        voteAccount: env.voteAddress(proposalId, stakeAccount),
+        proposal: env.proposalAddress(proposalId),
+        config: env.config,
+        stakingStats: env.stakingStats,
+        voter: voter.keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter.keypair])
+      .rpc();
+  }
+
+  describe("create_proposal", () => {
+    it("rejects a pass threshold above 100%", async () => {
+      await env.expectError(createProposal(10_001), "InvalidBps");
+    });
+  });
+
+  describe("vote_on_proposal", () => {
+    let holder: User;
+    let stakeAccount: PublicKey;
+
+    before(async () => {
+      holder = await env.newUser();
+      stakeAccount = await env.stake(holder, 1_000_000);
+      // Voting power is snapshotted at the proposal's slot, so stake in an earlier one
+      await env.warp(1);
+    });
+
+    it("records one vote per stake", async () => {
+      const proposalId = await createProposal();
+
+      await vote(proposalId, stakeAccount, holder);
+
+      const proposal = await env.program.account.proposal.fetch(env.proposalAddress(proposalId));
+      expect(proposal.voteCount.toNumber()).to.equal(1);
+      expect(proposal.yesWeight.toNumber()).to.equal(1_000);
+      expect(await env.exists(env.voteAddress(proposalId, stakeAccount))).to.be.true;
+    });
+
+    it("does not let a delegate vote again with an already voted stake", async () => {
+      const proposalId = await createProposal();
+      const delegate = await env.newUser(0);
+      await vote(proposalId, stakeAccount, holder);
+
+      await env.program.methods
+        .delegateVotes(delegate.keypair.publicKey)
+        .accounts({ stakeAccount, config: env.config, user: holder.keypair.publicKey })
+        .signers([holder.keypair])
+        .rpc();
+      await env.expectFailure(vote(proposalId, stakeAccount, delegate));
+
+      const proposal = await env.program.account.proposal.fetch(env.proposalAddress(proposalId));
+      expect(proposal.voteCount.toNumber()).to.equal(1);
+    });
+
+    it("rejects voters that neither own nor were delegated the stake", async () => {
+      const proposalId = await createProposal();
+      const outsider = await env.newUser(0);
+
+      await env.expectError(vote(proposalId, stakeAccount, outsider), "Unauthorized");
+    });
+  });
+
+  describe("finalize_proposal", () => {
+    let holder: User;
+    let stakeAccount: PublicKey;
+    let opponent: User;
+    let opposingStake: PublicKey;
+
+    before(async () => {
+      holder = await env.newUser();
+      stakeAccount = await env.stake(holder, 1_000_000);
+      opponent = await env.newUser();
+      opposingStake = await env.stake(opponent, 1_000_000);
+      await env.warp(1);
+    });
+
+    async function finalizeProposal(proposalId: number, proposerStake?: PublicKey) {
+      const proposal = env.proposalAddress(proposalId);
+      await env.program.methods
+        .finalizeProposal()
+        .accounts({
+          proposal,
+          proposerStake: proposerStake ?? (await env.program.account.proposal.fetch(proposal)).proposerStake,
+        })
+        .rpc();
+    }
+
+    it("records the outcome once voting ends and releases the proposer's bond", async () => {
+      const proposalId = await createProposal();
+      await vote(proposalId, stakeAccount, holder);
+      await env.expectError(finalizeProposal(proposalId), "VotingNotEnded");
+
+      await env.warp(DAY);
+      await finalizeProposal(proposalId);
+
+      const proposal = await env.program.account.proposal.fetch(env.proposalAddress(proposalId));
+      expect(proposal.finalized).to.be.true;
+      expect(proposal.passed).to.be.true;
+      expect((await env.fetchStake(proposal.proposerStake)).bondedProposal).to.be.null;
+      await env.expectError(finalizeProposal(proposalId), "ProposalAlreadyFinalized");
+    });
+
+    // Finalizes a proposal needing 60% yes after `yes` and `no` weight was cast
+    async function passesAtSixtyPercent(yes: number, no: number): Promise<boolean> {
+      const proposalId = await createProposal(6_000);
+      await vote(proposalId, stakeAccount, holder, yes, VOTE_YES);
+      await vote(proposalId, opposingStake, opponent, no, VOTE_NO);
+      await env.warp(DAY);
+
+      await finalizeProposal(proposalId);
+      return (await env.program.account.proposal.fetch(env.proposalAddress(proposalId))).passed;
+    }
+
+    it("fails a proposal with 59% yes against a 60% threshold", async () => {
+      expect(await passesAtSixtyPercent(590, 410)).to.be.false;
+    });
+
+    it("passes a proposal with 61% yes against a 60% threshold", async () => {
+      expect(await passesAtSixtyPercent(610, 390)).to.be.true;
+    });
+
+    it("fails a proposal nobody voted on", async () => {
+      const proposalId = await createProposal();
+      await env.warp(DAY);
+
+      await finalizeProposal(proposalId);
+
+      expect((await env.program.account.proposal.fetch(env.proposalAddress(proposalId))).passed).to.be.false;
+    });
+
+    it("rejects a stake other than the proposer's bond", async () => {
+      const proposalId = await createProposal();
+      await env.warp(DAY);
+
+      await env.expectError(finalizeProposal(proposalId, stakeAccount), "Unauthorized");
+    });
+  });
+});
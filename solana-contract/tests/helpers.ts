@@ -0,0 +1,630 @@
+import { BN, EventParser, LangErrorCode, Program } from "@coral-xyz/anchor";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+  createInitializeAccount3Instruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+
+import { IDL as SecurizzIDL, Securizz } from "../target/types/securizz";
+
+export const PROGRAM_ID = new PublicKey(require("../target/idl/securizz.json").metadata.address);
+
+export const DAY = 86_400;
+export const FEE_SPLIT_BPS = 3_000;
+export const MAX_BATCH = 10;
+
+export const ROLE_SUBMITTER = 0;
+export const ROLE_VERIFIER = 1;
+export const ROLE_BOTH = 2;
+
+export const METHOD_AUTOMATED = 0;
+export const METHOD_MANUAL = 1;
+
+export const VOTE_NO = 0;
+export const VOTE_YES = 1;
+export const VOTE_ABSTAIN = 2;
+
+export const NO_FINDINGS = {
+  reentrancy: 0,
+  integerOverflow: 0,
+  accessControl: 0,
+  uncheckedInput: 0,
+  panic: 0,
+  unsafeCode: 0,
+  raceCondition: 0,
+  resourceExhaustion: 0,
+};
+
+// 32 bytes derived from a label, for contract and report hashes
+export function hash32(label: string): number[] {
+  return [...createHash("sha256").update(label).digest()];
+}
+
+export interface ProofArgs {
+  contractHash: number[];
+  reportHash: number[];
+  ipfsCid: string;
+  riskScore: number;
+  contractAddress: PublicKey;
+  auditScore: number;
+  hashAlgo: number | null;
+  supersedes: number[] | null;
+  tags: { key: string; value: string }[];
+  findings: typeof NO_FINDINGS;
+  contentHash: number[];
+  pinAttestation: number[] | null;
+  reportCids: { kind: object; cid: string }[];
+  toolVersion: number;
+  upgradeAuthority: PublicKey | null;
+  auditMethod: number;
+  allowedVerifiers: PublicKey[];
+}
+
+export interface SubmittedProof {
+  address: PublicKey;
+  args: ProofArgs;
+}
+
+export interface User {
+  keypair: Keypair;
+  tokenAccount: PublicKey;
+}
+
+let labels = 0;
+
+export class TestEnv {
+  readonly admin: Keypair;
+  rewardMint: PublicKey;
+  treasury: PublicKey;
+  stakingPool: PublicKey;
+  rewardPool: PublicKey;
+
+  private constructor(
+    readonly context: ProgramTestContext,
+    readonly provider: BankrunProvider,
+    readonly program: Program<Securizz>
+  ) {
+    this.admin = context.payer;
+  }
+
+  // A fresh bank with the config, oracle registry, staking stats, reward mint
+  // and the pool and treasury token accounts the config points at
+  static async start(feeSplitBps = FEE_SPLIT_BPS): Promise<TestEnv> {
+    const context = await startAnchor(".", [], []);
+    const provider = new BankrunProvider(context);
+    const program = new Program<Securizz>(SecurizzIDL, PROGRAM_ID, provider);
+    const env = new TestEnv(context, provider, program);
+
+    await program.methods
+      .initializeConfig(feeSplitBps)
+      .accounts({ config: env.config, admin: env.admin.publicKey, systemProgram: SystemProgram.programId })
+      .rpc();
+    await program.methods
+      .initializeOracleRegistry(32)
+      .accounts({
+        oracleRegistry: env.registry,
+        config: env.config,
+        admin: env.admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .initializeStakingStats()
+      .accounts({ stakingStats: env.stakingStats, payer: env.admin.publicKey, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    env.rewardMint = await env.createMint();
+    env.treasury = await env.createTokenAccount(env.rewardMint, env.treasuryAuthority);
+    env.stakingPool = await env.createTokenAccount(env.rewardMint, env.stakingAuthority);
+    env.rewardPool = await env.createTokenAccount(env.rewardMint, env.stakingAuthority);
+    await env.updateConfig({
+      treasury: env.treasury,
+      stakingPool: env.stakingPool,
+      rewardPool: env.rewardPool,
+    });
+
+    return env;
+  }
+
+  // --- addresses ---
+
+  pda(...seeds: (Buffer | Uint8Array | number[])[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      seeds.map((seed) => Buffer.from(seed)),
+      PROGRAM_ID
+    )[0];
+  }
+
+  get config(): PublicKey {
+    return this.pda(Buffer.from("config"));
+  }
+
+  get registry(): PublicKey {
+    return this.pda(Buffer.from("oracle_registry"));
+  }
+
+  get stakingStats(): PublicKey {
+    return this.pda(Buffer.from("staking_stats"));
+  }
+
+  get stakingAuthority(): PublicKey {
+    return this.pda(Buffer.from("staking_authority"));
+  }
+
+  get treasuryAuthority(): PublicKey {
+    return this.pda(Buffer.from("treasury_authority"));
+  }
+
+  proofAddress(contractHash: number[], oracle: PublicKey): PublicKey {
+    return this.pda(Buffer.from("audit_proof"), contractHash, oracle.toBuffer());
+  }
+
+  reputationAddress(oracle: PublicKey): PublicKey {
+    return this.pda(Buffer.from("oracle_reputation"), oracle.toBuffer());
+  }
+
+  addressIndex(contractAddress: PublicKey): PublicKey {
+    return this.pda(Buffer.from("addr_index"), contractAddress.toBuffer());
+  }
+
+  auditHistory(contractAddress: PublicKey): PublicKey {
+    return this.pda(Buffer.from("audit_history"), contractAddress.toBuffer());
+  }
+
+  verificationHistory(proof: PublicKey): PublicKey {
+    return this.pda(Buffer.from("verification_history"), proof.toBuffer());
+  }
+
+  stakeAddress(user: PublicKey): PublicKey {
+    return this.pda(Buffer.from("stake"), user.toBuffer());
+  }
+
+  stakeIndex(user: PublicKey): PublicKey {
+    return this.pda(Buffer.from("stake_index"), user.toBuffer());
+  }
+
+  positionMint(stake: PublicKey): PublicKey {
+    return this.pda(Buffer.from("position"), stake.toBuffer());
+  }
+
+  withdrawalRequest(stake: PublicKey): PublicKey {
+    return this.pda(Buffer.from("withdrawal_request"), stake.toBuffer());
+  }
+
+  proposalAddress(proposalId: number): PublicKey {
+    return this.pda(Buffer.from("proposal"), new BN(proposalId).toArrayLike(Buffer, "le", 8));
+  }
+
+  voteAddress(proposalId: number, stakeAccount: PublicKey): PublicKey {
+    return this.pda(Buffer.from("vote"), new BN(proposalId).toArrayLike(Buffer, "le", 8), stakeAccount.toBuffer());
+  }
+
+  queuedAction(actionId: number): PublicKey {
+    return this.pda(Buffer.from("queued_action"), new BN(actionId).toArrayLike(Buffer, "le", 8));
+  }
+
+  escrowAddress(proof: PublicKey, payer: PublicKey): PublicKey {
+    return this.pda(Buffer.from("escrow"), proof.toBuffer(), payer.toBuffer());
+  }
+
+  escrowVault(escrow: PublicKey): PublicKey {
+    return this.pda(Buffer.from("escrow_vault"), escrow.toBuffer());
+  }
+
+  get treasuryConfig(): PublicKey {
+    return this.pda(Buffer.from("treasury_config"));
+  }
+
+  treasuryWithdrawal(withdrawalId: number): PublicKey {
+    return this.pda(Buffer.from("withdrawal"), new BN(withdrawalId).toArrayLike(Buffer, "le", 8));
+  }
+
+  // --- clock ---
+
+  async now(): Promise<number> {
+    const clock = await this.context.banksClient.getClock();
+    return Number(clock.unixTimestamp);
+  }
+
+  // Moves the clock forward and starts a new slot, which also gives identical
+  // transactions a fresh blockhash
+  async warp(seconds: number): Promise<void> {
+    const clock = await this.context.banksClient.getClock();
+    const slot = clock.slot + 1n;
+    this.context.warpToSlot(slot);
+    this.context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  // --- transactions ---
+
+  async blockhash(): Promise<string> {
+    const [blockhash] = await this.context.banksClient.getLatestBlockhash();
+    return blockhash;
+  }
+
+  async send(instructions: TransactionInstruction[], signers: Keypair[] = []): Promise<void> {
+    const tx = new Transaction().add(...instructions);
+    tx.recentBlockhash = await this.blockhash();
+    tx.feePayer = this.admin.publicKey;
+    tx.sign(this.admin, ...signers);
+    await this.context.banksClient.processTransaction(tx);
+  }
+
+  // Simulates a method, failing if it does not succeed
+  private async simulate(method: { transaction(): Promise<Transaction> }, signers: Keypair[]) {
+    const tx = await method.transaction();
+    tx.recentBlockhash = await this.blockhash();
+    tx.feePayer = this.admin.publicKey;
+    tx.sign(this.admin, ...signers);
+    const simulation = await this.context.banksClient.simulateTransaction(tx);
+    expect(simulation.result, simulation.meta?.logMessages.join("\n")).to.be.null;
+    return simulation;
+  }
+
+  // Simulates a method and returns what the program passed to set_return_data
+  async returnData(method: { transaction(): Promise<Transaction> }, signers: Keypair[] = []): Promise<Buffer> {
+    const simulation = await this.simulate(method, signers);
+    return Buffer.from(simulation.meta.returnData?.data ?? []);
+  }
+
+  // Simulates a method and returns the instruction's compute budget and the units
+  // left at each sol_log_compute_units call
+  async computeUnits(
+    method: { transaction(): Promise<Transaction> },
+    signers: Keypair[] = []
+  ): Promise<{ budget: number; remaining: number[] }> {
+    const logs = (await this.simulate(method, signers)).meta.logMessages;
+    const consumed = logs
+      .map((log) => new RegExp(`^Program ${this.program.programId} consumed \\d+ of (\\d+) compute units$`).exec(log))
+      .find((match) => match !== null);
+    return {
+      budget: Number(consumed[1]),
+      remaining: logs
+        .map((log) => /^Program consumption: (\d+) units remaining$/.exec(log))
+        .filter((match) => match !== null)
+        .map((match) => Number(match[1])),
+    };
+  }
+
+  // Simulates a method and returns the data of every `name` event it emitted
+  async events(
+    method: { transaction(): Promise<Transaction> },
+    name: string,
+    signers: Keypair[] = []
+  ): Promise<any[]> {
+    const simulation = await this.simulate(method, signers);
+    const parser = new EventParser(this.program.programId, this.program.coder);
+    return [...parser.parseLogs(simulation.meta.logMessages)]
+      .filter((event) => event.name === name)
+      .map((event) => event.data);
+  }
+
+  // Fails unless `promise` rejects with the named program or Anchor error
+  async expectError(promise: Promise<unknown>, name: string): Promise<void> {
+    const idlError = this.program.idl.errors?.find((error) => error.name === name);
+    const code = idlError?.code ?? LangErrorCode[name as keyof typeof LangErrorCode];
+    expect(code, `unknown error ${name}`).to.not.be.undefined;
+
+    try {
+      await promise;
+    } catch (err) {
+      const message = [String(err), ...(err.logs ?? [])].join("\n");
+      expect(message).to.match(new RegExp(`${name}|custom program error: 0x${code.toString(16)}\\b`, "i"));
+      return;
+    }
+    expect.fail(`expected ${name}`);
+  }
+
+  // For failures that surface as runtime rather than program errors, such as
+  // initializing an account that already exists
+  async expectFailure(promise: Promise<unknown>): Promise<void> {
+    try {
+      await promise;
+    } catch {
+      return;
+    }
+    expect.fail("expected the transaction to fail");
+  }
+
+  // --- config and oracles ---
+
+  async updateConfig(update: Record<string, unknown>): Promise<void> {
+    await this.program.methods
+      .updateConfig(update as any)
+      .accounts({ config: this.config, admin: this.admin.publicKey })
+      .rpc();
+  }
+
+  async fetchConfig() {
+    return this.program.account.programConfig.fetch(this.config);
+  }
+
+  async fund(keypair: Keypair, sol = 10): Promise<void> {
+    await this.send([
+      SystemProgram.transfer({
+        fromPubkey: this.admin.publicKey,
+        toPubkey: keypair.publicKey,
+        lamports: sol * LAMPORTS_PER_SOL,
+      }),
+    ]);
+  }
+
+  // A funded keypair with a reward-mint token account holding `tokens`
+  async newUser(tokens = 1_000_000_000): Promise<User> {
+    const keypair = Keypair.generate();
+    await this.fund(keypair);
+    const tokenAccount = await this.createTokenAccount(this.rewardMint, keypair.publicKey);
+    if (tokens > 0) {
+      await this.mintTo(this.rewardMint, tokenAccount, tokens);
+    }
+    return { keypair, tokenAccount };
+  }
+
+  async addOracle(oracle: PublicKey, role = ROLE_BOTH): Promise<void> {
+    await this.program.methods
+      .addOracle(oracle, role, false)
+      .accounts({ oracleRegistry: this.registry, config: this.config, admin: this.admin.publicKey })
+      .rpc();
+  }
+
+  // A registered oracle that has staked, so it can both submit and verify
+  async newOracle(role = ROLE_BOTH, stake = 1_000_000): Promise<User> {
+    const oracle = await this.newUser();
+    await this.addOracle(oracle.keypair.publicKey, role);
+    if (stake > 0) {
+      await this.stake(oracle, stake);
+    }
+    return oracle;
+  }
+
+  // --- proofs ---
+
+  async nextNonce(oracle: PublicKey): Promise<BN> {
+    const reputation = await this.program.account.oracleReputation.fetchNullable(this.reputationAddress(oracle));
+    return reputation?.nonce ?? new BN(0);
+  }
+
+  proofArgs(overrides: Partial<ProofArgs> = {}): ProofArgs {
+    const label = `proof-${labels++}`;
+    return {
+      contractHash: hash32(`${label}-contract`),
+      reportHash: hash32(`${label}-report`),
+      ipfsCid: `Qm${label}`,
+      riskScore: 20,
+      contractAddress: Keypair.generate().publicKey,
+      auditScore: 90,
+      hashAlgo: null,
+      supersedes: null,
+      tags: [],
+      findings: NO_FINDINGS,
+      contentHash: hash32(`${label}-content`),
+      pinAttestation: null,
+      reportCids: [],
+      toolVersion: 0x010000,
+      upgradeAuthority: null,
+      auditMethod: METHOD_MANUAL,
+      allowedVerifiers: [],
+      ...overrides,
+    };
+  }
+
+  submitProofMethod(oracle: User, args: ProofArgs, nonce: BN, accounts: Record<string, PublicKey | null> = {}) {
+    const oracleKey = oracle.keypair.publicKey;
+    return this.program.methods
+      .submitProof(
+        args.contractHash,
+        args.reportHash,
+        args.ipfsCid,
+        new BN(args.riskScore),
+        args.contractAddress,
+        args.auditScore,
+        args.hashAlgo,
+        args.supersedes,
+        args.tags,
+        args.findings,
+        args.contentHash,
+        nonce,
+        args.pinAttestation,
+        args.reportCids as any,
+        args.toolVersion,
+        args.upgradeAuthority,
+        args.auditMethod,
+        args.allowedVerifiers
+      )
+      .accounts({
+        auditProof: this.proofAddress(args.contractHash, oracleKey),
+        priorProof: null,
+        oracleRegistry: this.registry,
+        config: this.config,
+        oracleStake: null,
+        oracleReputation: this.reputationAddress(oracleKey),
+        addressIndex: this.addressIndex(args.contractAddress),
+        auditHistory: this.auditHistory(args.contractAddress),
+        oracleTokenAccount: null,
+        treasury: null,
+        oracle: oracleKey,
+        tokenProgram: null,
+        instructions: null,
+        systemProgram: SystemProgram.programId,
+        ...accounts,
+      })
+      .signers([oracle.keypair]);
+  }
+
+  async submitProof(
+    oracle: User,
+    overrides: Partial<ProofArgs> = {},
+    accounts: Record<string, PublicKey | null> = {}
+  ): Promise<SubmittedProof> {
+    const args = this.proofArgs(overrides);
+    const nonce = await this.nextNonce(oracle.keypair.publicKey);
+    await this.submitProofMethod(oracle, args, nonce, accounts).rpc();
+    return { address: this.proofAddress(args.contractHash, oracle.keypair.publicKey), args };
+  }
+
+  async fetchProof(address: PublicKey) {
+    return this.program.account.auditProof.fetch(address);
+  }
+
+  async signVerification(proof: PublicKey, verifier: User, verified = true) {
+    const auditProof = await this.fetchProof(proof);
+    await this.program.methods
+      .updateVerification(verified)
+      .accounts({
+        auditProof: proof,
+        oracleStake: this.stakeAddress(verifier.keypair.publicKey),
+        auditHistory: this.auditHistory(auditProof.contractAddress),
+        oracleRegistry: this.registry,
+        config: this.config,
+        authority: verifier.keypair.publicKey,
+      })
+      .signers([verifier.keypair])
+      .rpc();
+  }
+
+  // --- staking ---
+
+  async stake(user: User, amount: number, duration = DAY, gracePeriod = 0, cliffSeconds = 0): Promise<PublicKey> {
+    const userKey = user.keypair.publicKey;
+    const stakeAccount = this.stakeAddress(userKey);
+    const positionMint = this.positionMint(stakeAccount);
+    await this.program.methods
+      .stakeTokens(new BN(amount), new BN(duration), new BN(gracePeriod), new BN(cliffSeconds))
+      .accounts({
+        stakeAccount,
+        stakeIndex: this.stakeIndex(userKey),
+        userTokenAccount: user.tokenAccount,
+        stakingPool: this.stakingPool,
+        config: this.config,
+        stakingStats: this.stakingStats,
+        positionMint,
+        positionTokenAccount: getAssociatedTokenAddressSync(positionMint, userKey, true),
+        stakingAuthority: this.stakingAuthority,
+        user: userKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user.keypair])
+      .rpc();
+    return stakeAccount;
+  }
+
+  async fetchStake(address: PublicKey) {
+    return this.program.account.stakeAccount.fetch(address);
+  }
+
+  async refillRewardPool(amount: number): Promise<void> {
+    const funder = await this.newUser(amount);
+    await this.program.methods
+      .refillRewardPool(new BN(amount))
+      .accounts({
+        funderTokenAccount: funder.tokenAccount,
+        rewardPool: this.rewardPool,
+        config: this.config,
+        funder: funder.keypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([funder.keypair])
+      .rpc();
+  }
+
+  // --- tokens ---
+
+  async createMint(decimals = 6): Promise<PublicKey> {
+    const mint = Keypair.generate();
+    const rent = await this.context.banksClient.getRent();
+    await this.send(
+      [
+        SystemProgram.createAccount({
+          fromPubkey: this.admin.publicKey,
+          newAccountPubkey: mint.publicKey,
+          lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+          space: MINT_SIZE,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        createInitializeMint2Instruction(mint.publicKey, decimals, this.admin.publicKey, this.admin.publicKey),
+      ],
+      [mint]
+    );
+    return mint.publicKey;
+  }
+
+  // A plain (non-associated) token account, so PDAs can own one per mint
+  async createTokenAccount(mint: PublicKey, owner: PublicKey): Promise<PublicKey> {
+    const account = Keypair.generate();
+    const rent = await this.context.banksClient.getRent();
+    await this.send(
+      [
+        SystemProgram.createAccount({
+          fromPubkey: this.admin.publicKey,
+          newAccountPubkey: account.publicKey,
+          lamports: Number(rent.minimumBalance(BigInt(ACCOUNT_SIZE))),
+          space: ACCOUNT_SIZE,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        createInitializeAccount3Instruction(account.publicKey, mint, owner),
+      ],
+      [account]
+    );
+    return account.publicKey;
+  }
+
+  async mintTo(mint: PublicKey, destination: PublicKey, amount: number): Promise<void> {
+    await this.send([createMintToInstruction(mint, destination, this.admin.publicKey, amount)]);
+  }
+
+  async transferTokens(from: PublicKey, to: PublicKey, owner: Keypair, amount: number): Promise<void> {
+    await this.send([createTransferInstruction(from, to, owner.publicKey, amount)], [owner]);
+  }
+
+  async balance(tokenAccount: PublicKey): Promise<number> {
+    const account = await this.context.banksClient.getAccount(tokenAccount);
+    if (account === null) {
+      return 0;
+    }
+    return Number(AccountLayout.decode(account.data).amount);
+  }
+
+  async lamports(address: PublicKey): Promise<number> {
+    const account = await this.context.banksClient.getAccount(address);
+    return account === null ? 0 : Number(account.lamports);
+  }
+
+  async exists(address: PublicKey): Promise<boolean> {
+    const account = await this.context.banksClient.getAccount(address);
+    return account !== null && account.lamports > 0;
+  }
+}
+
+export { SYSVAR_INSTRUCTIONS_PUBKEY, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync };
@@ -0,0 +1,623 @@
+import { BN } from "@coral-xyz/anchor";
+import { Ed25519Program, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import {
+  DAY,
+  hash32,
+  MAX_BATCH,
+  NO_FINDINGS,
+  ProofArgs,
+  SubmittedProof,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  TestEnv,
+  TOKEN_PROGRAM_ID,
+  User,
+} from "./helpers";
+
+describe("proofs", () => {
+  let env: TestEnv;
+  let oracle: User;
+  let otherOracle: User;
+
+  before(async () => {
+    env = await TestEnv.start();
+    oracle = await env.newOracle();
+    otherOracle = await env.newOracle();
+  });
+
+  describe("supersedes", () => {
+    function supersede(submitter: User, prior: SubmittedProof, overrides = {}) {
+      return env.submitProof(
+        submitter,
+        { contractAddress: prior.args.contractAddress, supersedes: prior.args.contractHash, ...overrides },
+        { priorProof: prior.address }
+      );
+    }
+
+    it("links a two-step upgrade chain in both directions", async () => {
+      const first = await env.submitProof(oracle);
+      const second = await supersede(oracle, first);
+      const third = await supersede(oracle, second);
+
+      const [firstProof, secondProof, thirdProof] = await Promise.all(
+        [first, second, third].map((proof) => env.fetchProof(proof.address))
+      );
+      expect(firstProof.supersedes).to.be.null;
+      expect(firstProof.supersededBy).to.deep.equal(second.args.contractHash);
+      expect(secondProof.supersedes).to.deep.equal(first.args.contractHash);
+      expect(secondProof.supersededBy).to.deep.equal(third.args.contractHash);
+      expect(thirdProof.supersedes).to.deep.equal(second.args.contractHash);
+      expect(thirdProof.supersededBy).to.be.null;
+    });
+
+    it("rejects another oracle superseding the proof", async () => {
+      const victim = await env.submitProof(oracle);
+
+      await env.expectError(supersede(otherOracle, victim), "Unauthorized");
+
+      expect((await env.fetchProof(victim.address)).supersededBy).to.be.null;
+    });
+
+    it("rejects a prior proof for a different contract", async () => {
+      const prior = await env.submitProof(oracle);
+
+      await env.expectError(
+        env.submitProof(
+          oracle,
+          { supersedes: prior.args.contractHash },
+          { priorProof: prior.address }
+        ),
+        "ContractMismatch"
+      );
+    });
+
+    it("rejects a prior proof that does not match the superseded hash", async () => {
+      const prior = await env.submitProof(oracle);
+
+      await env.expectError(
+        env.submitProof(
+          oracle,
+          { contractAddress: prior.args.contractAddress, supersedes: hash32("unrelated") },
+          { priorProof: prior.address }
+        ),
+        "ProofNotFound"
+      );
+    });
+
+    it("rejects superseding a proof twice", async () => {
+      const prior = await env.submitProof(oracle);
+      await supersede(oracle, prior);
+
+      await env.expectError(supersede(oracle, prior), "AlreadySuperseded");
+    });
+  });
+
+  describe("submit_proof_attested", () => {
+    let relayer: User;
+
+    before(async () => {
+      relayer = await env.newUser();
+    });
+
+    // Mirrors AuditProof::attestation_message
+    function attestationMessage(args: ProofArgs, nonce: BN): Buffer {
+      const cid = Buffer.from(args.ipfsCid);
+      const cidLen = Buffer.alloc(4);
+      cidLen.writeUInt32LE(cid.length);
+      const toolVersion = Buffer.alloc(4);
+      toolVersion.writeUInt32LE(args.toolVersion);
+      return Buffer.concat([
+        Buffer.from(args.contractHash),
+        Buffer.from(args.reportHash),
+        cidLen,
+        cid,
+        new BN(args.riskScore).toArrayLike(Buffer, "le", 8),
+        args.contractAddress.toBuffer(),
+        Buffer.from([args.auditScore]),
+        Buffer.from(args.contentHash),
+        toolVersion,
+        Buffer.from([0]), // no upgrade authority
+        Buffer.from([args.auditMethod]),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ]);
+    }
+
+    // `tampered` signs something other than the submitted proof by its oracle
+    async function submitAttested(
+      oracle: User,
+      overrides: Partial<ProofArgs> = {},
+      accounts: Record<string, PublicKey | null> = {},
+      signedNonce?: BN,
+      tampered: { signer?: Keypair; signedArgs?: Partial<ProofArgs> } = {}
+    ): Promise<SubmittedProof> {
+      const oracleKey = oracle.keypair.publicKey;
+      const args = env.proofArgs(overrides);
+      const nonce = signedNonce ?? (await env.nextNonce(oracleKey));
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: (tampered.signer ?? oracle.keypair).secretKey,
+        message: attestationMessage({ ...args, ...tampered.signedArgs }, nonce),
+      });
+      // Single-signature layout: 16-byte offsets header, then pubkey, then signature
+      const signature = [...ed25519Ix.data.subarray(48, 112)];
+
+      await env.program.methods
+        .submitProofAttested(
+          args.contractHash,
+          args.reportHash,
+          args.ipfsCid,
+          new BN(args.riskScore),
+          args.contractAddress,
+          args.auditScore,
+          signature,
+          oracleKey,
+          args.contentHash,
+          args.toolVersion,
+          null,
+          args.auditMethod,
+          nonce
+        )
+        .accounts({
+          auditProof: env.proofAddress(args.contractHash, oracleKey),
+          oracleReputation: env.reputationAddress(oracleKey),
+          oracleRegistry: env.registry,
+          config: env.config,
+          oracleStake: env.stakeAddress(oracleKey),
+          addressIndex: env.addressIndex(args.contractAddress),
+          auditHistory: env.auditHistory(args.contractAddress),
+          submitterTokenAccount: null,
+          treasury: null,
+          submitter: relayer.keypair.publicKey,
+          tokenProgram: null,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          ...accounts,
+        })
+        .preInstructions([ed25519Ix])
+        .signers([relayer.keypair])
+        .rpc();
+      return { address: env.proofAddress(args.contractHash, oracleKey), args };
+    }
+
+    it("records a relayed proof in the address index and history", async () => {
+      const nonce = await env.nextNonce(oracle.keypair.publicKey);
+      const proof = await submitAttested(oracle);
+
+      const stored = await env.fetchProof(proof.address);
+      expect(stored.oracle.equals(oracle.keypair.publicKey)).to.be.true;
+      expect(await env.nextNonce(oracle.keypair.publicKey)).to.deep.equal(nonce.addn(1));
+
+      const index = await env.program.account.addressIndex.fetch(env.addressIndex(proof.args.contractAddress));
+      expect(index.contractHash).to.deep.equal(proof.args.contractHash);
+      const history = await env.program.account.auditHistory.fetch(env.auditHistory(proof.args.contractAddress));
+      expect(history.entries).to.have.lengthOf(1);
+    });
+
+    it("rejects a signature over different proof fields", async () => {
+      const attempt = submitAttested(oracle, { auditScore: 90 }, {}, undefined, { signedArgs: { auditScore: 40 } });
+
+      await env.expectError(attempt, "InvalidSignature");
+    });
+
+    it("rejects a signature by anyone but the named oracle", async () => {
+      const attempt = submitAttested(oracle, {}, {}, undefined, { signer: otherOracle.keypair });
+
+      await env.expectError(attempt, "InvalidSignature");
+    });
+
+    it("rejects a signature over an already used nonce", async () => {
+      const nonce = await env.nextNonce(oracle.keypair.publicKey);
+      await submitAttested(oracle, {}, {}, nonce);
+
+      await env.expectError(submitAttested(oracle, {}, {}, nonce), "InvalidNonce");
+    });
+
+    it("counts against the oracle's daily submission limit", async () => {
+      const limited = await env.newOracle();
+      await env.updateConfig({ maxSubmissionsPerDay: 1 });
+      try {
+        await submitAttested(limited);
+        await env.expectError(submitAttested(limited), "RateLimitExceeded");
+      } finally {
+        await env.updateConfig({ maxSubmissionsPerDay: 0 });
+      }
+    });
+
+    it("charges the relayer the submission fee for an under-staked oracle", async () => {
+      const fee = 1_000;
+      await env.updateConfig({ submissionFee: new BN(fee), minOracleStake: new BN(10_000_000) });
+      try {
+        await env.expectError(submitAttested(oracle), "SubmissionRequirementNotMet");
+
+        const treasuryBefore = await env.balance(env.treasury);
+        await submitAttested(oracle, {}, {
+          submitterTokenAccount: relayer.tokenAccount,
+          treasury: env.treasury,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        });
+        expect(await env.balance(env.treasury)).to.equal(treasuryBefore + fee);
+      } finally {
+        await env.updateConfig({ submissionFee: new BN(0), minOracleStake: new BN(0) });
+      }
+    });
+  });
+
+  describe("get_proof_data", () => {
+    const cid = (label: string) => `Qm${label}`.padEnd(100, "x");
+
+    async function proofData(proof: SubmittedProof) {
+      const data = await env.returnData(
+        env.program.methods.getProofData().accounts({ auditProof: proof.address, config: env.config })
+      );
+      // The account coder expects the 8-byte discriminator the return data leaves out
+      const decoded = env.program.coder.accounts.decodeUnchecked(
+        "AuditProof",
+        Buffer.concat([Buffer.alloc(8), data.subarray(1)])
+      );
+      return { truncated: data[0] === 1, length: data.length, decoded };
+    }
+
+    async function addReports(proof: SubmittedProof, count: number) {
+      const kinds = [{ summary: {} }, { detailed: {} }, { appendix: {} }, { appendix: {} }];
+      for (let i = 0; i < count; i++) {
+        await env.program.methods
+          .addReport(kinds[i] as any, cid(`report-${i}`))
+          .accounts({ auditProof: proof.address, oracle: oracle.keypair.publicKey })
+          .signers([oracle.keypair])
+          .rpc();
+      }
+    }
+
+    async function addTags(proof: SubmittedProof, count: number) {
+      for (let i = 0; i < count; i++) {
+        await env.program.methods
+          .addTag(`key-${i}`.padEnd(32, "k"), `value-${i}`.padEnd(32, "v"))
+          .accounts({ auditProof: proof.address, authority: oracle.keypair.publicKey })
+          .signers([oracle.keypair])
+          .rpc();
+      }
+    }
+
+    it("returns the whole proof, matching the on-chain account", async () => {
+      const proof = await env.submitProof(oracle, { ipfsCid: cid("main") });
+      await addReports(proof, 2);
+      await addTags(proof, 1);
+
+      const { truncated, decoded } = await proofData(proof);
+
+      expect(truncated).to.be.false;
+      expect(JSON.stringify(decoded)).to.equal(JSON.stringify(await env.fetchProof(proof.address)));
+    });
+
+    it("truncates the CID to the return data limit and flags it", async () => {
+      const proof = await env.submitProof(oracle, { ipfsCid: cid("main") });
+      await addReports(proof, 4);
+      await addTags(proof, 3);
+
+      const { truncated, length, decoded } = await proofData(proof);
+
+      expect(truncated).to.be.true;
+      expect(length).to.equal(1024);
+      expect(decoded.ipfsCid.length).to.be.lessThan(100);
+      expect(cid("main").startsWith(decoded.ipfsCid)).to.be.true;
+      const account = await env.fetchProof(proof.address);
+      expect(JSON.stringify({ ...decoded, ipfsCid: account.ipfsCid })).to.equal(JSON.stringify(account));
+    });
+
+    it("fails when the proof does not fit even without its CID", async () => {
+      const proof = await env.submitProof(oracle, { ipfsCid: cid("main") });
+      await addReports(proof, 4);
+      await addTags(proof, 5);
+
+      await env.expectError(
+        env.program.methods.getProofData().accounts({ auditProof: proof.address, config: env.config }).rpc(),
+        "ReturnDataTooLarge"
+      );
+    });
+  });
+
+
+  describe("close_proof", () => {
+    const CLOSE_FEE_BPS = 1_000;
+
+    before(async () => {
+      await env.updateConfig({ closeFeeBps: CLOSE_FEE_BPS });
+    });
+
+    after(async () => {
+      await env.updateConfig({ closeFeeBps: 0 });
+    });
+
+    function revoke(proof: SubmittedProof) {
+      return env.program.methods
+        .revokeProof()
+        .accounts({ auditProof: proof.address, config: env.config, admin: env.admin.publicKey })
+        .rpc();
+    }
+
+    function closeProof(proof: SubmittedProof, signer: User = oracle) {
+      return env.program.methods
+        .closeProof()
+        .accounts({
+          auditProof: proof.address,
+          addressIndex: env.addressIndex(proof.args.contractAddress),
+          treasury: env.treasury,
+          config: env.config,
+          oracle: signer.keypair.publicKey,
+        })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    it("splits the reclaimed rent between the treasury and the oracle and drops the address index", async () => {
+      const proof = await env.submitProof(oracle);
+      const index = env.addressIndex(proof.args.contractAddress);
+      await revoke(proof);
+      const [proofRent, indexRent, treasuryBefore, oracleBefore] = await Promise.all(
+        [proof.address, index, env.treasury, oracle.keypair.publicKey].map((address) => env.lamports(address))
+      );
+
+      await closeProof(proof);
+
+      const fee = Math.floor((proofRent * CLOSE_FEE_BPS) / 10_000);
+      expect(await env.lamports(env.treasury)).to.equal(treasuryBefore + fee);
+      expect(await env.lamports(oracle.keypair.publicKey)).to.equal(oracleBefore + proofRent - fee + indexRent);
+      expect(await env.exists(proof.address)).to.be.false;
+      expect(await env.exists(index)).to.be.false;
+    });
+
+    it("keeps an address index that already points at the successor", async () => {
+      const prior = await env.submitProof(oracle);
+      const successor = await env.submitProof(
+        oracle,
+        { contractAddress: prior.args.contractAddress, supersedes: prior.args.contractHash },
+        { priorProof: prior.address }
+      );
+
+      await closeProof(prior);
+
+      const index = await env.program.account.addressIndex.fetch(env.addressIndex(prior.args.contractAddress));
+      expect(index.contractHash).to.deep.equal(successor.args.contractHash);
+    });
+
+    it("rejects proofs that are still active", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(closeProof(proof), "ProofStillActive");
+    });
+
+    it("rejects anyone but the proof's oracle", async () => {
+      const proof = await env.submitProof(oracle);
+      await revoke(proof);
+
+      await env.expectError(closeProof(proof, otherOracle), "Unauthorized");
+    });
+  });
+
+  describe("proof fields", () => {
+    function setRiskScore(proof: SubmittedProof, riskScore: number, signer = oracle) {
+      return env.program.methods
+        .setRiskScore(new BN(riskScore))
+        .accounts({ auditProof: proof.address, oracle: signer.keypair.publicKey })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    function setAuditScore(proof: SubmittedProof, auditScore: number, signer = oracle) {
+      return env.program.methods
+        .setAuditScore(auditScore)
+        .accounts({ auditProof: proof.address, oracle: signer.keypair.publicKey })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    it("updates the scores and drops the proof back to unverified", async () => {
+      const proof = await env.submitProof(oracle);
+      await env.signVerification(proof.address, oracle);
+      const { version } = await env.fetchProof(proof.address);
+
+      await setRiskScore(proof, 35);
+      await setAuditScore(proof, 70);
+
+      const updated = await env.fetchProof(proof.address);
+      expect(updated.riskScore.toNumber()).to.equal(35);
+      expect(updated.auditScore).to.equal(70);
+      expect(updated.verified).to.be.false;
+      expect(updated.version).to.equal(version + 2);
+    });
+
+    it("rejects scores out of range", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(setRiskScore(proof, 101), "InvalidRiskScore");
+      await env.expectError(setAuditScore(proof, 101), "InvalidAuditScore");
+    });
+
+    it("rejects anyone but the proof's oracle", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(setRiskScore(proof, 35, otherOracle), "Unauthorized");
+      await env.expectError(setAuditScore(proof, 70, otherOracle), "Unauthorized");
+    });
+  });
+
+  describe("migrate_proof", () => {
+    function migrateProof(proof: SubmittedProof, authority: Keypair, proofOracle = oracle.keypair.publicKey) {
+      return env.program.methods
+        .migrateProof(proof.args.contractHash, proofOracle)
+        .accounts({
+          auditProof: proof.address,
+          config: env.config,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("leaves proofs already on the current layout alone", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(migrateProof(proof, oracle.keypair), "AlreadyMigrated");
+      await env.expectError(migrateProof(proof, env.admin), "AlreadyMigrated");
+    });
+
+    it("rejects anyone but the proof's oracle and the admin", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(migrateProof(proof, otherOracle.keypair), "Unauthorized");
+    });
+
+    it("rejects an account that is not the proof's PDA", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(migrateProof(proof, oracle.keypair, otherOracle.keypair.publicKey), "ConstraintSeeds");
+    });
+  });
+
+  describe("proof reads", () => {
+    it("decays the effective score by the configured rate per day", async () => {
+      const proof = await env.submitProof(oracle, { auditScore: 90 });
+      await env.updateConfig({ decayBpsPerDay: 1_000 });
+      try {
+        await env.warp(2 * DAY);
+
+        const data = await env.returnData(
+          env.program.methods.getEffectiveScore().accounts({ auditProof: proof.address, config: env.config })
+        );
+        expect(data[0]).to.equal(72);
+      } finally {
+        await env.updateConfig({ decayBpsPerDay: 0 });
+      }
+    });
+
+    it("reports whether the audited program is upgradeable", async () => {
+      const upgradeAuthority = Keypair.generate().publicKey;
+      const mutable = await env.submitProof(oracle, { upgradeAuthority });
+      const immutable = await env.submitProof(oracle);
+
+      const status = async (proof: SubmittedProof) =>
+        (
+          await env.events(
+            env.program.methods.checkMutability().accounts({ auditProof: proof.address, config: env.config }),
+            "MutabilityStatus"
+          )
+        )[0];
+
+      const mutableStatus = await status(mutable);
+      expect(mutableStatus.mutable).to.be.true;
+      expect(mutableStatus.upgradeAuthority.equals(upgradeAuthority)).to.be.true;
+      expect((await status(immutable)).mutable).to.be.false;
+    });
+
+    it("reports whether a finding category was recorded", async () => {
+      const proof = await env.submitProof(oracle, { findings: { ...NO_FINDINGS, reentrancy: 2 } });
+
+      const hasCategory = async (category: number) =>
+        (
+          await env.returnData(
+            env.program.methods.hasCategory(category).accounts({ auditProof: proof.address, config: env.config })
+          )
+        )[0] === 1;
+
+      expect(await hasCategory(0)).to.be.true;
+      expect(await hasCategory(1)).to.be.false;
+    });
+
+    it("resolves a contract address to its latest proof and history", async () => {
+      const proof = await env.submitProof(oracle);
+      await env.signVerification(proof.address, oracle);
+
+      const [resolved] = await env.events(
+        env.program.methods
+          .resolveByAddress()
+          .accounts({ addressIndex: env.addressIndex(proof.args.contractAddress) }),
+        "AddressResolved"
+      );
+      expect(resolved.contractHash).to.deep.equal(proof.args.contractHash);
+      expect(resolved.oracle.equals(oracle.keypair.publicKey)).to.be.true;
+
+      const [history] = await env.events(
+        env.program.methods.getHistory().accounts({ auditHistory: env.auditHistory(proof.args.contractAddress) }),
+        "AuditHistoryRetrieved"
+      );
+      expect(history.entries).to.have.length(2);
+      expect(history.entries[1].verified).to.be.true;
+    });
+
+    it("summarizes up to MAX_BATCH proofs and rejects more", async () => {
+      const proofs: SubmittedProof[] = [];
+      for (let i = 0; i <= MAX_BATCH; i++) {
+        proofs.push(await env.submitProof(oracle));
+      }
+      const summary = (batch: SubmittedProof[]) =>
+        env.program.methods
+          .getProofsSummary(0, 255)
+          .remainingAccounts(batch.map((proof) => ({ pubkey: proof.address, isWritable: false, isSigner: false })));
+
+      // 32-byte contract address, audit score and verified flag per proof
+      const data = await env.returnData(summary(proofs.slice(0, MAX_BATCH)));
+      expect(data.length).to.equal(MAX_BATCH * 34);
+      expect(new PublicKey(data.subarray(0, 32)).equals(proofs[0].args.contractAddress)).to.be.true;
+
+      await env.expectError(summary(proofs).rpc(), "AccountCountMismatch");
+    });
+
+    it("looks up whether a proof exists without loading it", async () => {
+      const proof = await env.submitProof(oracle);
+      const missing = hash32("never submitted");
+
+      const lookup = async (contractHash: number[], method: "findProof" | "proofExists") =>
+        (
+          await env.returnData(
+            env.program.methods[method](contractHash, oracle.keypair.publicKey).accounts({
+              auditProof: env.proofAddress(contractHash, oracle.keypair.publicKey),
+            })
+          )
+        )[0] === 1;
+
+      expect(await lookup(proof.args.contractHash, "findProof")).to.be.true;
+      expect(await lookup(proof.args.contractHash, "proofExists")).to.be.true;
+      expect(await lookup(missing, "findProof")).to.be.false;
+      expect(await lookup(missing, "proofExists")).to.be.false;
+    });
+
+    it("compares a re-audit against the earlier proof of the same contract", async () => {
+      const original = await env.submitProof(oracle, { auditScore: 70, riskScore: 40 });
+      const reaudit = await env.submitProof(otherOracle, {
+        contractAddress: original.args.contractAddress,
+        auditScore: 85,
+        riskScore: 40,
+      });
+
+      const [comparison] = await env.events(
+        env.program.methods.compareProofs().accounts({ oldProof: original.address, newProof: reaudit.address }),
+        "ProofComparison"
+      );
+      expect(comparison.oldScore).to.equal(70);
+      expect(comparison.newScore).to.equal(85);
+      expect(comparison.improved).to.be.true;
+
+      const unrelated = await env.submitProof(oracle);
+      await env.expectError(
+        env.program.methods.compareProofs().accounts({ oldProof: original.address, newProof: unrelated.address }).rpc(),
+        "ContractMismatch"
+      );
+    });
+
+    it("dry-runs the submission field checks", async () => {
+      const validate = async (auditScore: number, riskScore: number, ipfsCid: string) => {
+        const [validation] = await env.events(
+          env.program.methods.validateSubmission(auditScore, new BN(riskScore), ipfsCid).accounts({}),
+          "SubmissionValidation"
+        );
+        return validation;
+      };
+
+      expect((await validate(90, 20, "QmValid")).ok).to.be.true;
+      const invalid = await validate(90, 101, "QmValid");
+      expect(invalid.ok).to.be.false;
+      expect(invalid.failingField).to.equal("risk_score");
+    });
+  });
+});
@@ -0,0 +1,218 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { SubmittedProof, TestEnv, TOKEN_PROGRAM_ID, User } from "./helpers";
+
+describe("rewards", () => {
+  let env: TestEnv;
+  let oracle: User;
+
+  before(async () => {
+    env = await TestEnv.start();
+    oracle = await env.newOracle();
+  });
+
+  describe("oracle_reward", () => {
+    async function verifiedProof(): Promise<SubmittedProof> {
+      const proof = await env.submitProof(oracle);
+      await env.signVerification(proof.address, oracle);
+      return proof;
+    }
+
+    function oracleReward(proof: SubmittedProof, amount: number, fromTreasury = false, admin = env.admin) {
+      return env.program.methods
+        .oracleReward(new BN(amount))
+        .accounts({
+          auditProof: proof.address,
+          oracleTokenAccount: oracle.tokenAccount,
+          mint: fromTreasury ? null : env.rewardMint,
+          treasury: fromTreasury ? env.treasury : null,
+          treasuryAuthority: env.treasuryAuthority,
+          config: env.config,
+          admin: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    it("mints the reward once per proof", async () => {
+      const proof = await verifiedProof();
+      const before = await env.balance(oracle.tokenAccount);
+
+      await oracleReward(proof, 1_000);
+
+      expect(await env.balance(oracle.tokenAccount)).to.equal(before + 1_000);
+      expect((await env.fetchProof(proof.address)).oracleRewarded).to.be.true;
+      await env.expectError(oracleReward(proof, 1_000), "OracleAlreadyRewarded");
+    });
+
+    it("rejects unverified proofs", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(oracleReward(proof, 1_000), "ProofNotVerified");
+    });
+
+    it("rejects callers other than the admin", async () => {
+      const proof = await verifiedProof();
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+
+      await env.expectError(oracleReward(proof, 1_000, false, outsider), "Unauthorized");
+    });
+
+    it("caps treasury-funded rewards at the configured maximum", async () => {
+      await env.updateConfig({ oracleRewardSource: { treasury: {} }, maxOracleReward: new BN(500) });
+      await env.mintTo(env.rewardMint, env.treasury, 10_000);
+      try {
+        const proof = await verifiedProof();
+        await env.expectError(oracleReward(proof, 501, true), "RewardAboveCap");
+
+        const before = await env.balance(oracle.tokenAccount);
+        await oracleReward(proof, 500, true);
+        expect(await env.balance(oracle.tokenAccount)).to.equal(before + 500);
+      } finally {
+        await env.updateConfig({ oracleRewardSource: { mint: {} }, maxOracleReward: new BN(0) });
+      }
+    });
+  });
+
+  describe("sla_refund", () => {
+    const AMOUNT = 100_000;
+
+    interface ReleasedEscrow {
+      escrow: PublicKey;
+      payer: User;
+      proof: SubmittedProof;
+    }
+
+    before(async () => {
+      await env.mintTo(env.rewardMint, env.treasury, 1_000_000);
+    });
+
+    // Funds an escrow for a verified proof scoring 90 and releases it to the oracle
+    async function releasedEscrow(slaScore: number | null): Promise<ReleasedEscrow> {
+      const payer = await env.newUser();
+      const proof = await env.submitProof(oracle, { auditScore: 90 });
+      const escrow = env.escrowAddress(proof.address, payer.keypair.publicKey);
+      await env.program.methods
+        .escrowAuditPayment(new BN(AMOUNT), null, slaScore)
+        .accounts({
+          auditProof: proof.address,
+          escrow,
+          escrowVault: env.escrowVault(escrow),
+          mint: env.rewardMint,
+          userTokenAccount: payer.tokenAccount,
+          user: payer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([payer.keypair])
+        .rpc();
+      await env.signVerification(proof.address, oracle);
+      await env.program.methods
+        .releaseEscrow()
+        .accounts({
+          auditProof: proof.address,
+          escrow,
+          escrowVault: env.escrowVault(escrow),
+          oracleTokenAccount: oracle.tokenAccount,
+          treasury: env.treasury,
+          payerTokenAccount: payer.tokenAccount,
+          config: env.config,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      return { escrow, payer, proof };
+    }
+
+    function slaRefund(escrow: PublicKey, payer: User, proof: SubmittedProof, admin = env.admin) {
+      return env.program.methods
+        .slaRefund()
+        .accounts({
+          auditProof: proof.address,
+          escrow,
+          treasury: env.treasury,
+          treasuryAuthority: env.treasuryAuthority,
+          payerTokenAccount: payer.tokenAccount,
+          config: env.config,
+          admin: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    it("refunds the shortfall against the SLA agreed at funding", async () => {
+      const { escrow, payer, proof } = await releasedEscrow(100);
+      const before = await env.balance(payer.tokenAccount);
+
+      await slaRefund(escrow, payer, proof);
+
+      expect(await env.balance(payer.tokenAccount)).to.equal(before + AMOUNT / 10);
+      expect((await env.program.account.escrow.fetch(escrow)).slaRefunded).to.be.true;
+      await env.expectError(slaRefund(escrow, payer, proof), "SlaAlreadyRefunded");
+    });
+
+    it("pays nothing when the audit exactly meets the SLA", async () => {
+      const { escrow, payer, proof } = await releasedEscrow(90);
+
+      await env.expectError(slaRefund(escrow, payer, proof), "SlaNotBreached");
+    });
+
+    it("rejects escrows funded without an SLA", async () => {
+      const { escrow, payer, proof } = await releasedEscrow(null);
+
+      await env.expectError(slaRefund(escrow, payer, proof), "NoSlaAgreed");
+    });
+
+    it("rejects callers other than the admin", async () => {
+      const { escrow, payer, proof } = await releasedEscrow(100);
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+
+      await env.expectError(slaRefund(escrow, payer, proof, outsider), "Unauthorized");
+    });
+  });
+
+  describe("initialize_token", () => {
+    const tokenMetaAddress = (mint: Keypair) => env.pda(Buffer.from("token_meta"), mint.publicKey.toBuffer());
+
+    function initializeToken(mint: Keypair, decimals: number) {
+      return env.program.methods
+        .initializeToken(decimals)
+        .accounts({
+          mint: mint.publicKey,
+          tokenMeta: tokenMetaAddress(mint),
+          authority: env.admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([mint])
+        .rpc();
+    }
+
+    it("records who initialized the mint and when", async () => {
+      const mint = Keypair.generate();
+
+      await initializeToken(mint, 9);
+
+      const meta = await env.program.account.tokenMeta.fetch(tokenMetaAddress(mint));
+      expect(meta.mint.equals(mint.publicKey)).to.be.true;
+      expect(meta.authority.equals(env.admin.publicKey)).to.be.true;
+      expect(meta.decimals).to.equal(9);
+      expect(meta.initializedAt.toNumber()).to.equal(await env.now());
+    });
+
+    it("cannot initialize the same mint twice", async () => {
+      const mint = Keypair.generate();
+      await initializeToken(mint, 9);
+
+      await env.expectFailure(initializeToken(mint, 6));
+      expect((await env.program.account.tokenMeta.fetch(tokenMetaAddress(mint))).decimals).to.equal(9);
+    });
+  });
+});
@@ -0,0 +1,578 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import {
+  DAY,
+  FEE_SPLIT_BPS,
+  getAssociatedTokenAddressSync,
+  TestEnv,
+  TOKEN_PROGRAM_ID,
+  User,
+} from "./helpers";
+
+// Units settle_rewards may use in claim_rewards. The math is closed form, so this
+// holds however long a stake has accrued; a per-day loop would blow through it
+const REWARD_MATH_CU_BUDGET = 5_000;
+
+describe("staking", () => {
+  let env: TestEnv;
+
+  describe("auto_restake", () => {
+    const KEEPER_FEE = 100;
+    const MIN_PRINCIPAL = 10_000;
+    let keeper: User;
+
+    before(async () => {
+      env = await TestEnv.start();
+      await env.refillRewardPool(1_000_000_000);
+      await env.updateConfig({ keeperFee: new BN(KEEPER_FEE), minRestakePrincipal: new BN(MIN_PRINCIPAL) });
+      keeper = await env.newUser(0);
+    });
+
+    function autoRestake(stakeAccount: PublicKey) {
+      return env.program.methods
+        .autoRestake()
+        .accounts({
+          stakeAccount,
+          rewardPool: env.rewardPool,
+          keeperTokenAccount: keeper.tokenAccount,
+          stakingAuthority: env.stakingAuthority,
+          stakingStats: env.stakingStats,
+          config: env.config,
+          keeper: keeper.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper.keypair])
+        .rpc();
+    }
+
+    it("re-locks a stake left past its grace window and pays the keeper from its rewards", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000, DAY, DAY);
+      await env.expectError(autoRestake(stakeAccount), "GracePeriodNotElapsed");
+
+      await env.warp(2 * DAY + 1);
+      const keeperBefore = await env.balance(keeper.tokenAccount);
+      await autoRestake(stakeAccount);
+
+      const now = await env.now();
+      const stake = await env.fetchStake(stakeAccount);
+      expect(stake.unlockTime.toNumber()).to.equal(now + DAY);
+      expect(stake.stakedAt.toNumber()).to.equal(now);
+      expect(stake.rewardsClaimed.toNumber()).to.equal(0);
+      expect(stake.accruedRewards.toNumber()).to.be.greaterThan(0);
+      expect(await env.balance(keeper.tokenAccount)).to.equal(keeperBefore + KEEPER_FEE);
+
+      // The new lock has to run its course before another restake
+      await env.expectError(autoRestake(stakeAccount), "GracePeriodNotElapsed");
+    });
+
+    it("rejects stakes below the minimum principal", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, MIN_PRINCIPAL - 1, DAY);
+      await env.warp(DAY + 1);
+
+      await env.expectError(autoRestake(stakeAccount), "RestakeBelowMinimum");
+    });
+  });
+
+  describe("activity bonus", () => {
+    const ORACLE_STAKE = 1_000_000;
+    let oracle: User;
+
+    before(async () => {
+      env = await TestEnv.start();
+      await env.refillRewardPool(1_000_000_000);
+      await env.updateConfig({ activityBonusBps: 10_000 });
+      oracle = await env.newOracle(undefined, ORACLE_STAKE);
+    });
+
+    async function payForAudit(fee: number) {
+      const payer = await env.newUser();
+      const proof = await env.submitProof(oracle);
+      await env.program.methods
+        .payForAudit(new BN(fee))
+        .accounts({
+          auditProof: proof.address,
+          userTokenAccount: payer.tokenAccount,
+          treasury: env.treasury,
+          treasuryAuthority: env.treasuryAuthority,
+          rewardPool: env.rewardPool,
+          config: env.config,
+          stakingStats: env.stakingStats,
+          user: payer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+    }
+
+    async function claimedActivityRewards(user: User, stakeAccount: PublicKey): Promise<number> {
+      const [claimed] = await env.events(
+        env.program.methods.claimRewards(null).accounts({
+          stakeAccount,
+          userTokenAccount: user.tokenAccount,
+          rewardPool: env.rewardPool,
+          stakingAuthority: env.stakingAuthority,
+          config: env.config,
+          stakingStats: env.stakingStats,
+          auditorBadge: null,
+          destination: null,
+          user: user.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }),
+        "RewardsClaimed",
+        [user.keypair]
+      );
+      return claimed.activityRewards.toNumber();
+    }
+
+    it("splits a 3000 bps fee 30/70 between the reward pool and the treasury", async () => {
+      const poolBefore = await env.balance(env.rewardPool);
+      const treasuryBefore = await env.balance(env.treasury);
+
+      await payForAudit(100_000);
+
+      expect((await env.balance(env.rewardPool)) - poolBefore).to.equal(30_000);
+      expect((await env.balance(env.treasury)) - treasuryBefore).to.equal(70_000);
+    });
+
+    it("pays a pro-rata share of the staker fees from audits paid mid-stake", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+
+      await payForAudit(100_000);
+      await env.warp(DAY + 1);
+
+      // 30% of the fee reaches the pool, shared with the oracle's equal stake
+      const toStakers = (100_000 * FEE_SPLIT_BPS) / 10_000;
+      expect(await claimedActivityRewards(user, stakeAccount)).to.equal(toStakers / 2);
+    });
+
+    it("pays nothing for dust audits that put no fees in the pool", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+
+      for (let i = 0; i < 5; i++) {
+        await payForAudit(1);
+      }
+      await env.warp(DAY + 1);
+
+      expect(await claimedActivityRewards(user, stakeAccount)).to.equal(0);
+    });
+  });
+
+  describe("position ownership", () => {
+    const AMOUNT = 1_000_000;
+    let seller: User;
+    let buyer: User;
+    let stakeAccount: PublicKey;
+    let positionMint: PublicKey;
+    let sellerPosition: PublicKey;
+    let buyerPosition: PublicKey;
+
+    beforeEach(async () => {
+      env = await TestEnv.start();
+      seller = await env.newUser();
+      buyer = await env.newUser(0);
+      stakeAccount = await env.stake(seller, AMOUNT);
+      positionMint = env.positionMint(stakeAccount);
+      sellerPosition = getAssociatedTokenAddressSync(positionMint, seller.keypair.publicKey, true);
+      buyerPosition = await env.createTokenAccount(positionMint, buyer.keypair.publicKey);
+    });
+
+    async function withdraw(holder: User, positionTokenAccount: PublicKey) {
+      const stake = await env.fetchStake(stakeAccount);
+      await env.program.methods
+        .withdrawStake()
+        .accounts({
+          stakeAccount,
+          stakeIndex: env.stakeIndex(stake.user),
+          positionMint,
+          positionTokenAccount,
+          userTokenAccount: holder.tokenAccount,
+          stakingPool: env.stakingPool,
+          stakingAuthority: env.stakingAuthority,
+          config: env.config,
+          stakingStats: env.stakingStats,
+          user: holder.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([holder.keypair])
+        .rpc();
+    }
+
+    it("lets whoever holds the position withdraw after a plain token transfer", async () => {
+      await env.transferTokens(sellerPosition, buyerPosition, seller.keypair, 1);
+      await env.warp(DAY + 1);
+
+      await env.expectError(withdraw(seller, sellerPosition), "Unauthorized");
+
+      await withdraw(buyer, buyerPosition);
+      expect(await env.balance(buyer.tokenAccount)).to.equal(AMOUNT);
+      expect(await env.exists(stakeAccount)).to.be.false;
+    });
+
+    it("moves the position with transfer_stake so the seller can no longer withdraw", async () => {
+      await env.program.methods
+        .transferStake(buyer.keypair.publicKey)
+        .accounts({
+          stakeAccount,
+          fromIndex: env.stakeIndex(seller.keypair.publicKey),
+          toIndex: env.stakeIndex(buyer.keypair.publicKey),
+          positionMint,
+          positionTokenAccount: sellerPosition,
+          newOwnerPositionAccount: buyerPosition,
+          user: seller.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([seller.keypair])
+        .rpc();
+      expect(await env.balance(sellerPosition)).to.equal(0);
+      expect(await env.balance(buyerPosition)).to.equal(1);
+      await env.warp(DAY + 1);
+
+      await env.expectError(withdraw(seller, sellerPosition), "Unauthorized");
+
+      await withdraw(buyer, buyerPosition);
+      expect(await env.balance(buyer.tokenAccount)).to.equal(AMOUNT);
+    });
+  });
+
+  describe("claim_rewards", () => {
+    before(async () => {
+      env = await TestEnv.start();
+      await env.refillRewardPool(1_000_000_000);
+    });
+
+    function claimMethod(user: User, stakeAccount: PublicKey) {
+      return env.program.methods.claimRewards(null).accounts({
+        stakeAccount,
+        userTokenAccount: user.tokenAccount,
+        rewardPool: env.rewardPool,
+        stakingAuthority: env.stakingAuthority,
+        config: env.config,
+        stakingStats: env.stakingStats,
+        auditorBadge: null,
+        destination: null,
+        user: user.keypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+    }
+
+    function claimRewards(user: User, stakeAccount: PublicKey) {
+      return claimMethod(user, stakeAccount).signers([user.keypair]).rpc();
+    }
+
+    function setHold(method: "holdStake" | "releaseStake", user: User, admin = env.admin) {
+      return env.program.methods[method](user.keypair.publicKey)
+        .accounts({
+          stakeAccount: env.stakeAddress(user.keypair.publicKey),
+          config: env.config,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    it("previews nothing until the lock ends, then what a claim would pay", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+      const preview = async () =>
+        (
+          await env.events(
+            env.program.methods
+              .claimablePreview()
+              .accounts({ stakeAccount, config: env.config, stakingStats: env.stakingStats }),
+            "ClaimablePreview"
+          )
+        )[0];
+
+      const locked = await preview();
+      expect(locked.claimable.toNumber()).to.equal(0);
+      expect(locked.nextClaimTime.toNumber()).to.equal((await env.fetchStake(stakeAccount)).unlockTime.toNumber());
+
+      await env.warp(DAY + 1);
+      const unlocked = await preview();
+      expect(unlocked.claimable.toNumber()).to.be.greaterThan(0);
+
+      const before = await env.balance(user.tokenAccount);
+      await claimRewards(user, stakeAccount);
+      expect(await env.balance(user.tokenAccount)).to.equal(before + unlocked.claimable.toNumber());
+    });
+
+    it("pays nothing before the cliff", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000, DAY, 0, 3 * DAY);
+      await env.warp(DAY + 1);
+
+      await env.expectError(claimRewards(user, stakeAccount), "CliffNotReached");
+
+      await env.warp(3 * DAY);
+      await claimRewards(user, stakeAccount);
+    });
+
+    it("spaces claims by the configured cooldown", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+      await env.updateConfig({ claimCooldown: new BN(DAY) });
+      try {
+        await env.warp(DAY + 1);
+        await claimRewards(user, stakeAccount);
+
+        await env.warp(DAY / 2);
+        await env.expectError(claimRewards(user, stakeAccount), "ClaimCooldownActive");
+
+        await env.warp(DAY / 2);
+        await claimRewards(user, stakeAccount);
+      } finally {
+        await env.updateConfig({ claimCooldown: new BN(0) });
+      }
+    });
+
+    it("blocks claims on a held stake until the admin releases it", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+      await env.warp(DAY + 1);
+
+      await setHold("holdStake", user);
+      expect((await env.fetchStake(stakeAccount)).held).to.be.true;
+      await env.expectError(claimRewards(user, stakeAccount), "StakeHeld");
+
+      await setHold("releaseStake", user);
+      await claimRewards(user, stakeAccount);
+    });
+
+    it("rejects holds and releases by anyone but the admin", async () => {
+      const user = await env.newUser();
+      await env.stake(user, 1_000_000);
+
+      await env.expectError(setHold("holdStake", user, user.keypair), "Unauthorized");
+      await env.expectError(setHold("releaseStake", user, user.keypair), "Unauthorized");
+    });
+
+    it("rejects claims on someone else's stake", async () => {
+      const user = await env.newUser();
+      const outsider = await env.newUser(0);
+      const stakeAccount = await env.stake(user, 1_000_000);
+      await env.warp(DAY + 1);
+
+      await env.expectError(claimRewards(outsider, stakeAccount), "Unauthorized");
+    });
+
+    it("keeps the reward math within its compute budget however long the stake ran", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+      const rewardMathUnits = async () => {
+        const { remaining } = await env.computeUnits(claimMethod(user, stakeAccount), [user.keypair]);
+        return remaining[0] - remaining[1];
+      };
+
+      await env.warp(DAY + 1);
+      const afterOneDay = await rewardMathUnits();
+      await env.warp(4 * 365 * DAY);
+      const afterFourYears = await rewardMathUnits();
+
+      expect(afterOneDay).to.be.at.most(REWARD_MATH_CU_BUDGET);
+      expect(afterFourYears).to.be.at.most(REWARD_MATH_CU_BUDGET);
+    });
+  });
+
+  describe("withdrawal requests", () => {
+    const AMOUNT = 1_000_000;
+
+    before(async () => {
+      env = await TestEnv.start();
+      await env.updateConfig({ withdrawalDelay: new BN(DAY) });
+    });
+
+    function requestWithdrawal(stakeAccount: PublicKey, owner: User, signer: User, amount: number) {
+      const positionMint = env.positionMint(stakeAccount);
+      return env.program.methods
+        .requestWithdrawal(new BN(amount))
+        .accounts({
+          stakeAccount,
+          withdrawalRequest: env.withdrawalRequest(stakeAccount),
+          positionMint,
+          positionTokenAccount: getAssociatedTokenAddressSync(positionMint, owner.keypair.publicKey, true),
+          config: env.config,
+          stakingStats: env.stakingStats,
+          user: signer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    function claimWithdrawal(stakeAccount: PublicKey, signer: User) {
+      return env.program.methods
+        .claimWithdrawal()
+        .accounts({
+          withdrawalRequest: env.withdrawalRequest(stakeAccount),
+          userTokenAccount: signer.tokenAccount,
+          stakingPool: env.stakingPool,
+          stakingAuthority: env.stakingAuthority,
+          config: env.config,
+          user: signer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    it("pays out part of the principal once the withdrawal delay has passed", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, AMOUNT);
+      await env.expectError(requestWithdrawal(stakeAccount, user, user, 400_000), "StakeNotUnlocked");
+
+      await env.warp(DAY + 1);
+      const before = await env.balance(user.tokenAccount);
+      await requestWithdrawal(stakeAccount, user, user, 400_000);
+      expect((await env.fetchStake(stakeAccount)).amount.toNumber()).to.equal(AMOUNT - 400_000);
+
+      await env.expectError(claimWithdrawal(stakeAccount, user), "TimelockNotElapsed");
+
+      await env.warp(DAY);
+      await claimWithdrawal(stakeAccount, user);
+      expect(await env.balance(user.tokenAccount)).to.equal(before + 400_000);
+      expect(await env.exists(env.withdrawalRequest(stakeAccount))).to.be.false;
+    });
+
+    it("rejects requests for more than the stake holds", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, AMOUNT);
+      await env.warp(DAY + 1);
+
+      await env.expectError(requestWithdrawal(stakeAccount, user, user, AMOUNT + 1), "InsufficientStake");
+    });
+
+    it("rejects requests and claims by anyone but the position holder", async () => {
+      const user = await env.newUser();
+      const outsider = await env.newUser(0);
+      const stakeAccount = await env.stake(user, AMOUNT);
+      await env.warp(DAY + 1);
+
+      await env.expectError(requestWithdrawal(stakeAccount, user, outsider, 1_000), "Unauthorized");
+
+      await requestWithdrawal(stakeAccount, user, user, 1_000);
+      await env.warp(DAY);
+      await env.expectError(claimWithdrawal(stakeAccount, outsider), "Unauthorized");
+    });
+  });
+
+  describe("lock management", () => {
+    before(async () => {
+      env = await TestEnv.start();
+    });
+
+    function extendLock(stakeAccount: PublicKey, signer: User, newDuration: number) {
+      return env.program.methods
+        .extendLock(new BN(newDuration))
+        .accounts({
+          stakeAccount,
+          config: env.config,
+          stakingStats: env.stakingStats,
+          user: signer.keypair.publicKey,
+        })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    function changeDelegation(stakeAccount: PublicKey, signer: User, delegate: PublicKey | null) {
+      const method =
+        delegate === null ? env.program.methods.revokeDelegation() : env.program.methods.delegateVotes(delegate);
+      return method
+        .accounts({ stakeAccount, config: env.config, user: signer.keypair.publicKey })
+        .signers([signer.keypair])
+        .rpc();
+    }
+
+    it("extends the lock into a longer duration's tier", async () => {
+      const user = await env.newUser();
+      const stakeAccount = await env.stake(user, 1_000_000);
+
+      await extendLock(stakeAccount, user, 90 * DAY);
+
+      const stake = await env.fetchStake(stakeAccount);
+      expect(stake.unlockTime.toNumber()).to.equal((await env.now()) + 90 * DAY);
+      expect(stake.rewardRateBps).to.equal(125);
+      await env.expectError(extendLock(stakeAccount, user, 30 * DAY), "InvalidDuration");
+    });
+
+    it("hands voting power back when the owner revokes a delegation", async () => {
+      const user = await env.newUser();
+      const delegate = await env.newUser(0);
+      const stakeAccount = await env.stake(user, 1_000_000);
+
+      await changeDelegation(stakeAccount, user, delegate.keypair.publicKey);
+      await changeDelegation(stakeAccount, user, null);
+
+      expect((await env.fetchStake(stakeAccount)).delegate).to.be.null;
+      await env.expectError(changeDelegation(stakeAccount, user, null), "NotDelegated");
+    });
+
+    it("rejects lock and delegation changes by anyone but the owner", async () => {
+      const user = await env.newUser();
+      const outsider = await env.newUser(0);
+      const stakeAccount = await env.stake(user, 1_000_000);
+      await changeDelegation(stakeAccount, user, outsider.keypair.publicKey);
+
+      await env.expectError(extendLock(stakeAccount, outsider, 90 * DAY), "Unauthorized");
+      await env.expectError(changeDelegation(stakeAccount, outsider, null), "Unauthorized");
+    });
+  });
+
+  describe("slash_oracle", () => {
+    let oracle: User;
+    let stakeAccount: PublicKey;
+
+    before(async () => {
+      env = await TestEnv.start();
+      await env.updateConfig({ slashDistribution: { burnBps: 5_000, treasuryBps: 3_000, rewardsBps: 2_000 } });
+      oracle = await env.newOracle(undefined, 1_000_000);
+      stakeAccount = env.stakeAddress(oracle.keypair.publicKey);
+    });
+
+    function slashOracle(amount: number, admin = env.admin) {
+      return env.program.methods
+        .slashOracle(new BN(amount))
+        .accounts({
+          stakeAccount,
+          stakingPool: env.stakingPool,
+          rewardPool: env.rewardPool,
+          treasury: env.treasury,
+          mint: env.rewardMint,
+          stakingAuthority: env.stakingAuthority,
+          config: env.config,
+          stakingStats: env.stakingStats,
+          admin: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    it("splits the slashed stake between burn, treasury and reward pool", async () => {
+      const [pool, treasury, rewards] = await Promise.all(
+        [env.stakingPool, env.treasury, env.rewardPool].map((account) => env.balance(account))
+      );
+
+      await slashOracle(10_000);
+
+      expect((await env.fetchStake(stakeAccount)).amount.toNumber()).to.equal(990_000);
+      expect(await env.balance(env.stakingPool)).to.equal(pool - 10_000);
+      expect(await env.balance(env.treasury)).to.equal(treasury + 3_000);
+      expect(await env.balance(env.rewardPool)).to.equal(rewards + 2_000);
+    });
+
+    it("rejects slashing more than the stake holds", async () => {
+      await env.expectError(slashOracle(10_000_000), "InsufficientStake");
+    });
+
+    it("rejects callers other than the admin", async () => {
+      await env.expectError(slashOracle(1_000, oracle.keypair), "Unauthorized");
+    });
+  });
+});
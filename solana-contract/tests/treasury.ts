@@ -0,0 +1,120 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { TestEnv, TOKEN_PROGRAM_ID } from "./helpers";
+
+describe("treasury", () => {
+  let env: TestEnv;
+  let signers: Keypair[];
+  let keys: PublicKey[];
+
+  beforeEach(async () => {
+    env = await TestEnv.start();
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    keys = signers.map((signer) => signer.publicKey);
+    for (const signer of signers) {
+      await env.fund(signer);
+    }
+  });
+
+  function initializeTreasuryConfig(signerKeys: PublicKey[], threshold: number, admin = env.admin) {
+    return env.program.methods
+      .initializeTreasuryConfig(signerKeys, threshold)
+      .accounts({
+        treasuryConfig: env.treasuryConfig,
+        config: env.config,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  describe("initialize_treasury_config", () => {
+    it("stores the signer set and threshold", async () => {
+      await initializeTreasuryConfig(keys, 2);
+
+      const treasuryConfig = await env.program.account.treasuryConfig.fetch(env.treasuryConfig);
+      expect(treasuryConfig.signers).to.have.length(3);
+      expect(treasuryConfig.threshold).to.equal(2);
+    });
+
+    it("rejects thresholds the signer set can't meet and duplicate signers", async () => {
+      await env.expectError(initializeTreasuryConfig(keys, 0), "InvalidThreshold");
+      await env.expectError(initializeTreasuryConfig(keys, 4), "InvalidThreshold");
+      await env.expectError(initializeTreasuryConfig([keys[0], keys[0]], 1), "DuplicateSigner");
+    });
+
+    it("rejects callers other than the admin", async () => {
+      await env.expectError(initializeTreasuryConfig(keys, 2, signers[0]), "Unauthorized");
+    });
+  });
+
+  describe("treasury withdrawals", () => {
+    const AMOUNT = 1_000;
+    let recipient: PublicKey;
+
+    beforeEach(async () => {
+      await initializeTreasuryConfig(keys, 2);
+      await env.mintTo(env.rewardMint, env.treasury, 10_000);
+      recipient = (await env.newUser(0)).tokenAccount;
+    });
+
+    async function proposeWithdrawal(proposer: Keypair): Promise<number> {
+      const withdrawalId = (
+        await env.program.account.treasuryConfig.fetch(env.treasuryConfig)
+      ).withdrawalCount.toNumber();
+      await env.program.methods
+        .proposeWithdrawal(new BN(AMOUNT), recipient)
+        .accounts({
+          treasuryConfig: env.treasuryConfig,
+          withdrawal: env.treasuryWithdrawal(withdrawalId),
+          proposer: proposer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([proposer])
+        .rpc();
+      return withdrawalId;
+    }
+
+    function approveWithdrawal(withdrawalId: number, signer: Keypair) {
+      return env.program.methods
+        .approveWithdrawal()
+        .accounts({
+          treasuryConfig: env.treasuryConfig,
+          withdrawal: env.treasuryWithdrawal(withdrawalId),
+          treasury: env.treasury,
+          recipient,
+          treasuryAuthority: env.treasuryAuthority,
+          config: env.config,
+          signer: signer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("pays out once the threshold of signers approves", async () => {
+      const withdrawalId = await proposeWithdrawal(signers[0]);
+
+      await approveWithdrawal(withdrawalId, signers[0]);
+      expect(await env.balance(recipient)).to.equal(0);
+      await env.expectError(approveWithdrawal(withdrawalId, signers[0]), "AlreadyApproved");
+
+      await approveWithdrawal(withdrawalId, signers[1]);
+      expect(await env.balance(recipient)).to.equal(AMOUNT);
+      await env.expectError(approveWithdrawal(withdrawalId, signers[2]), "WithdrawalAlreadyExecuted");
+    });
+
+    it("rejects proposals and approvals from outside the signer set", async () => {
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+
+      await env.expectError(proposeWithdrawal(outsider), "Unauthorized");
+
+      const withdrawalId = await proposeWithdrawal(signers[0]);
+      await env.expectError(approveWithdrawal(withdrawalId, outsider), "Unauthorized");
+    });
+  });
+});
@@ -0,0 +1,427 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { DAY, SubmittedProof, TestEnv, TOKEN_PROGRAM_ID, User } from "./helpers";
+
+const HIGH_RISK = 80;
+const SINGLE_SIGNATURE = {
+  mediumRiskFrom: new BN(0),
+  highRiskFrom: new BN(0),
+  lowSignatures: 1,
+  mediumSignatures: 1,
+  highSignatures: 1,
+};
+const HIGH_RISK_NEEDS_TWO = { ...SINGLE_SIGNATURE, highRiskFrom: new BN(HIGH_RISK), highSignatures: 2 };
+// Units check_verified may spend validating its accounts. Checking the proof PDA
+// against its stored bump is one create_program_address call (1,500 units), where
+// searching for the canonical bump costs another 1,500 for every bump tried first
+const CHECK_VERIFIED_ACCOUNTS_CU_BUDGET = 20_000;
+
+describe("verification", () => {
+  let env: TestEnv;
+  let oracle: User;
+  let otherOracle: User;
+
+  before(async () => {
+    env = await TestEnv.start();
+    oracle = await env.newOracle();
+    otherOracle = await env.newOracle();
+  });
+
+  describe("verify_batch", () => {
+    function verifyBatch(authority: User, proofs: SubmittedProof[], hashes: number[][], skipMismatches: boolean) {
+      return env.program.methods
+        .verifyBatch(hashes, skipMismatches)
+        .accounts({
+          oracleRegistry: env.registry,
+          config: env.config,
+          oracleStake: env.stakeAddress(authority.keypair.publicKey),
+          authority: authority.keypair.publicKey,
+        })
+        .remainingAccounts(proofs.map((proof) => ({ pubkey: proof.address, isWritable: true, isSigner: false })))
+        .signers([authority.keypair])
+        .rpc();
+    }
+
+    async function verified(address: PublicKey): Promise<boolean> {
+      return (await env.fetchProof(address)).verified;
+    }
+
+    it("verifies every proof whose report hash matches", async () => {
+      const proofs = [await env.submitProof(oracle), await env.submitProof(oracle)];
+
+      await verifyBatch(
+        oracle,
+        proofs,
+        proofs.map((proof) => proof.args.reportHash),
+        false
+      );
+
+      for (const proof of proofs) {
+        expect(await verified(proof.address)).to.be.true;
+      }
+    });
+
+    it("skips mismatched proofs when asked to", async () => {
+      const matching = await env.submitProof(oracle);
+      const mismatched = await env.submitProof(oracle);
+
+      await verifyBatch(oracle, [matching, mismatched], [matching.args.reportHash, matching.args.reportHash], true);
+
+      expect(await verified(matching.address)).to.be.true;
+      expect(await verified(mismatched.address)).to.be.false;
+    });
+
+    it("rejects the whole batch on a mismatch otherwise", async () => {
+      const matching = await env.submitProof(oracle);
+      const mismatched = await env.submitProof(oracle);
+
+      await env.expectError(
+        verifyBatch(oracle, [matching, mismatched], [matching.args.reportHash, matching.args.reportHash], false),
+        "HashMismatch"
+      );
+      expect(await verified(matching.address)).to.be.false;
+    });
+
+    it("rejects proofs submitted by another oracle", async () => {
+      const proof = await env.submitProof(otherOracle);
+
+      await env.expectError(verifyBatch(oracle, [proof], [proof.args.reportHash], false), "Unauthorized");
+    });
+
+    it("rejects a hash count that differs from the proof count", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await env.expectError(
+        verifyBatch(oracle, [proof], [proof.args.reportHash, proof.args.reportHash], false),
+        "AccountCountMismatch"
+      );
+    });
+
+    it("rejects unregistered signers", async () => {
+      const proof = await env.submitProof(oracle);
+      const outsider = await env.newUser();
+      await env.stake(outsider, 1_000_000);
+
+      await env.expectError(verifyBatch(outsider, [proof], [proof.args.reportHash], false), "Unauthorized");
+    });
+
+    it("rejects signers below the minimum oracle stake", async () => {
+      const proof = await env.submitProof(oracle);
+      await env.updateConfig({ minOracleStake: new BN(10_000_000) });
+      try {
+        await env.expectError(
+          verifyBatch(oracle, [proof], [proof.args.reportHash], false),
+          "InsufficientOracleStake"
+        );
+      } finally {
+        await env.updateConfig({ minOracleStake: new BN(0) });
+      }
+    });
+
+    it("only signs proofs whose risk band needs more than one signature", async () => {
+      await env.updateConfig({ riskBands: HIGH_RISK_NEEDS_TWO });
+      try {
+        const proof = await env.submitProof(oracle, { riskScore: HIGH_RISK });
+
+        await verifyBatch(oracle, [proof], [proof.args.reportHash], false);
+
+        const pending = await env.fetchProof(proof.address);
+        expect(pending.verified).to.be.false;
+        expect(pending.verifierSignatures).to.have.lengthOf(1);
+
+        await env.signVerification(proof.address, otherOracle);
+        expect(await verified(proof.address)).to.be.true;
+      } finally {
+        await env.updateConfig({ riskBands: SINGLE_SIGNATURE });
+      }
+    });
+  });
+
+  describe("risk band quorum", () => {
+    it("verifies a low-risk proof with one signature and a high-risk one with two", async () => {
+      await env.updateConfig({ riskBands: HIGH_RISK_NEEDS_TWO });
+      try {
+        const lowRisk = await env.submitProof(oracle, { riskScore: 10 });
+        const highRisk = await env.submitProof(oracle, { riskScore: HIGH_RISK });
+
+        await env.signVerification(lowRisk.address, otherOracle);
+        await env.signVerification(highRisk.address, otherOracle);
+        expect((await env.fetchProof(lowRisk.address)).verified).to.be.true;
+        expect((await env.fetchProof(highRisk.address)).verified).to.be.false;
+
+        await env.signVerification(highRisk.address, oracle);
+        expect((await env.fetchProof(highRisk.address)).verified).to.be.true;
+      } finally {
+        await env.updateConfig({ riskBands: SINGLE_SIGNATURE });
+      }
+    });
+
+    it("rejects a quorum completed after the consensus window", async () => {
+      await env.updateConfig({ riskBands: HIGH_RISK_NEEDS_TWO, consensusWindow: new BN(DAY) });
+      try {
+        const proof = await env.submitProof(oracle, { riskScore: HIGH_RISK });
+        await env.signVerification(proof.address, otherOracle);
+        await env.warp(2 * DAY);
+
+        await env.expectError(env.signVerification(proof.address, oracle), "ConsensusExpired");
+      } finally {
+        await env.updateConfig({ riskBands: SINGLE_SIGNATURE, consensusWindow: new BN(0) });
+      }
+    });
+  });
+
+  describe("check_verified", () => {
+    async function checkVerified(proof: SubmittedProof): Promise<boolean> {
+      const data = await env.returnData(
+        env.program.methods.checkVerified().accounts({ auditProof: proof.address, config: env.config })
+      );
+      return data[0] === 1;
+    }
+
+    async function checkVerifiedByAddress(proof: SubmittedProof, auditProof = proof.address): Promise<boolean> {
+      const data = await env.returnData(
+        env.program.methods.checkVerifiedByAddress().accounts({
+          addressIndex: env.addressIndex(proof.args.contractAddress),
+          auditProof,
+          config: env.config,
+        })
+      );
+      return data[0] === 1;
+    }
+
+    it("validates the proof PDA against its stored bump within budget", async () => {
+      const proof = await env.submitProof(oracle);
+
+      const { budget, remaining } = await env.computeUnits(
+        env.program.methods.checkVerified().accounts({ auditProof: proof.address, config: env.config })
+      );
+      expect(budget - remaining[0]).to.be.at.most(CHECK_VERIFIED_ACCOUNTS_CU_BUDGET);
+    });
+
+    it("reports a verified proof by proof and by contract address", async () => {
+      const proof = await env.submitProof(oracle);
+      expect(await checkVerified(proof)).to.be.false;
+      expect(await checkVerifiedByAddress(proof)).to.be.false;
+
+      await env.signVerification(proof.address, oracle);
+
+      expect(await checkVerified(proof)).to.be.true;
+      expect(await checkVerifiedByAddress(proof)).to.be.true;
+    });
+
+    it("reports a proof past its expiry timestamp as unverified", async () => {
+      await env.updateConfig({ verificationTtl: new BN(DAY) });
+      const proof = await env.submitProof(oracle);
+      await env.signVerification(proof.address, oracle);
+      await env.updateConfig({ verificationTtl: new BN(0) });
+      expect(await checkVerified(proof)).to.be.true;
+
+      await env.warp(2 * DAY);
+
+      expect(await checkVerified(proof)).to.be.false;
+      expect(await checkVerifiedByAddress(proof)).to.be.false;
+    });
+
+    it("reports a proof as stale only once the TTL has fully elapsed", async () => {
+      await env.updateConfig({ verificationTtl: new BN(DAY) });
+      try {
+        const proof = await env.submitProof(oracle);
+        await env.signVerification(proof.address, oracle);
+        const verifiedAt = (await env.fetchProof(proof.address)).verificationTimestamp.toNumber();
+        const accounts = { auditProof: proof.address, config: env.config };
+
+        // Both reads should agree on staleness at every point around the boundary
+        const staleAt = async (time: number) => {
+          await env.warp(time - (await env.now()));
+          const [check] = await env.events(env.program.methods.checkVerified().accounts(accounts), "VerifiedCheck");
+          const [retrieved] = await env.events(env.program.methods.getProof().accounts(accounts), "ProofRetrieved");
+          expect(retrieved.stale).to.equal(check.stale);
+          return check.stale;
+        };
+
+        expect(await staleAt(verifiedAt + DAY - 1)).to.be.false;
+        expect(await staleAt(verifiedAt + DAY)).to.be.false;
+        expect(await staleAt(verifiedAt + DAY + 1)).to.be.true;
+      } finally {
+        await env.updateConfig({ verificationTtl: new BN(0) });
+      }
+    });
+
+    it("rejects a proof the address index does not point at", async () => {
+      const indexed = await env.submitProof(oracle);
+      const other = await env.submitProof(oracle);
+
+      await env.expectError(checkVerifiedByAddress(indexed, other.address), "ConstraintSeeds");
+    });
+  });
+
+  describe("verify_audit_integrity", () => {
+    function integrityCheck(proof: SubmittedProof, checkCommitment: boolean, contentHash = proof.args.contentHash) {
+      return env.program.methods.verifyAuditIntegrity(proof.args.reportHash, checkCommitment, contentHash).accounts({
+        auditProof: proof.address,
+        oracleStake: env.stakeAddress(oracle.keypair.publicKey),
+        oracleRegistry: env.registry,
+        config: env.config,
+        authority: oracle.keypair.publicKey,
+      });
+    }
+
+    function verifyIntegrity(proof: SubmittedProof, checkCommitment: boolean, contentHash?: number[]) {
+      return integrityCheck(proof, checkCommitment, contentHash).signers([oracle.keypair]).rpc();
+    }
+
+    function setIpfsCid(proof: SubmittedProof, ipfsCid: string) {
+      return env.program.methods
+        .setIpfsCid(ipfsCid)
+        .accounts({ auditProof: proof.address, oracle: oracle.keypair.publicKey })
+        .signers([oracle.keypair])
+        .rpc();
+    }
+
+    it("accepts an untouched proof against its commitment", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await verifyIntegrity(proof, true);
+
+      expect((await env.fetchProof(proof.address)).verified).to.be.true;
+    });
+
+    it("rejects a proof whose ipfs_cid was changed after submission", async () => {
+      const proof = await env.submitProof(oracle);
+      await setIpfsCid(proof, "QmTampered");
+
+      await env.expectError(verifyIntegrity(proof, true), "HashMismatch");
+    });
+
+    it("rejects a report whose content hash does not match", async () => {
+      const proof = await env.submitProof(oracle);
+      const swapped = [...proof.args.contentHash];
+      swapped[0] ^= 1;
+
+      await env.expectError(verifyIntegrity(proof, false, swapped), "HashMismatch");
+      expect((await env.fetchProof(proof.address)).verified).to.be.false;
+    });
+
+    it("emits ContentVerified only for a matching report", async () => {
+      const proof = await env.submitProof(oracle);
+
+      const [event] = await env.events(integrityCheck(proof, false), "ContentVerified", [oracle.keypair]);
+      expect(event.matched).to.be.true;
+      expect(event.contractHash).to.deep.equal(proof.args.contractHash);
+    });
+
+    it("accepts an edited proof once sealing commits to the edit", async () => {
+      const proof = await env.submitProof(oracle);
+      await setIpfsCid(proof, "QmCorrected");
+      await env.program.methods
+        .sealProof()
+        .accounts({ auditProof: proof.address, oracle: oracle.keypair.publicKey })
+        .signers([oracle.keypair])
+        .rpc();
+
+      await verifyIntegrity(proof, true);
+    });
+  });
+
+  describe("set_verified", () => {
+    function setVerified(proof: SubmittedProof, verified: boolean, admin = env.admin) {
+      return env.program.methods
+        .setVerified(verified, `Qm-override-${verified}`)
+        .accounts({
+          auditProof: proof.address,
+          verificationHistory: env.verificationHistory(proof.address),
+          auditHistory: env.auditHistory(proof.args.contractAddress),
+          config: env.config,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    it("lets the admin override verification and records each change", async () => {
+      const proof = await env.submitProof(oracle);
+
+      await setVerified(proof, true);
+      expect((await env.fetchProof(proof.address)).verified).to.be.true;
+
+      await setVerified(proof, false);
+      const auditProof = await env.fetchProof(proof.address);
+      expect(auditProof.verified).to.be.false;
+      expect(auditProof.verifierSignatures).to.be.empty;
+
+      const history = await env.program.account.verificationHistory.fetch(env.verificationHistory(proof.address));
+      expect(history.entries.map((entry) => entry.verified)).to.deep.equal([true, false]);
+      expect(history.entries[1].reasonCid).to.equal("Qm-override-false");
+    });
+
+    it("rejects callers other than the admin", async () => {
+      const proof = await env.submitProof(oracle);
+      const outsider = Keypair.generate();
+      await env.fund(outsider);
+
+      await env.expectError(setVerified(proof, true, outsider), "Unauthorized");
+    });
+  });
+
+  describe("sweep_expired", () => {
+    const SWEEP_FEE = 100;
+    let keeper: User;
+
+    before(async () => {
+      keeper = await env.newUser(0);
+      await env.mintTo(env.rewardMint, env.treasury, 10_000);
+      await env.updateConfig({ expirySweepFee: new BN(SWEEP_FEE) });
+    });
+
+    after(async () => {
+      await env.updateConfig({ expirySweepFee: new BN(0) });
+    });
+
+    async function expiringProof(): Promise<SubmittedProof> {
+      await env.updateConfig({ verificationTtl: new BN(DAY) });
+      const proof = await env.submitProof(oracle);
+      await env.signVerification(proof.address, oracle);
+      await env.updateConfig({ verificationTtl: new BN(0) });
+      return proof;
+    }
+
+    function sweepExpired(proof: SubmittedProof, keeperTokenAccount = keeper.tokenAccount) {
+      return env.program.methods
+        .sweepExpired()
+        .accounts({
+          auditProof: proof.address,
+          treasury: env.treasury,
+          keeperTokenAccount,
+          treasuryAuthority: env.treasuryAuthority,
+          config: env.config,
+          keeper: keeper.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper.keypair])
+        .rpc();
+    }
+
+    it("flags a lapsed proof once and pays the keeper", async () => {
+      const proof = await expiringProof();
+      await env.expectError(sweepExpired(proof), "ProofNotExpired");
+
+      await env.warp(2 * DAY);
+      const before = await env.balance(keeper.tokenAccount);
+      await sweepExpired(proof);
+
+      expect((await env.fetchProof(proof.address)).expired).to.be.true;
+      expect(await env.balance(keeper.tokenAccount)).to.equal(before + SWEEP_FEE);
+      await env.expectError(sweepExpired(proof), "AlreadySwept");
+    });
+
+    it("only pays into a token account the keeper owns", async () => {
+      const proof = await expiringProof();
+      await env.warp(2 * DAY);
+
+      await env.expectError(sweepExpired(proof, oracle.tokenAccount), "Unauthorized");
+    });
+  });
+});